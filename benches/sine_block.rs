@@ -0,0 +1,15 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_generate_sine_block(c: &mut Criterion) {
+    let phases: Vec<f32> = (0..1024).map(|i| (i as f32 / 1024.0) % 1.0).collect();
+    let mut output = vec![0.0f32; 1024];
+
+    c.bench_function("generate_sine_block/1024", |b| {
+        b.iter(|| {
+            audio_theorem::core::synth::audio::generate_sine_block(&phases, &mut output);
+        })
+    });
+}
+
+criterion_group!(benches, bench_generate_sine_block);
+criterion_main!(benches);
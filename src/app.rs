@@ -0,0 +1,1563 @@
+pub mod ab_compare;
+pub mod autosave;
+pub mod export;
+pub mod keyboard_shortcuts;
+pub mod midi_log_filter;
+pub mod settings;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::Stream;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::core::analyzer::Analyzer;
+use crate::core::midi::activity_log::{MidiActivityLog, MidiMessageType};
+use crate::core::midi::file_player::{MidiFilePlayer, PlaybackEvent};
+use crate::core::midi::input::{self, MidiMessage, MpeZone};
+use crate::core::midi::sysex::SysExHandler;
+use crate::core::midi::{MidiControlTarget, MidiSystem};
+use crate::core::macros::{default_macros, MacroKnob, MacroRoute};
+use crate::core::oscillator::{Oscillator, OscillatorCombinationMode};
+use crate::core::sequencer::{AutomationParameter, AutomationTrack};
+use crate::core::synth::preset::SynthPreset;
+use crate::core::synth::samples::{load_sample, ChannelConversion, CustomWavetable};
+use crate::core::synth::{RetriggerMode, Synth, VelocityScalingConfig};
+use crate::core::theory::Scale;
+pub use settings::AppSettings;
+
+/// How many callbacks the CPU load meter averages over.
+const CPU_LOAD_WINDOW: usize = 60;
+/// Consecutive overloaded callbacks before `SynthMessage::AudioOverload` fires.
+const OVERLOAD_STREAK_THRESHOLD: u32 = 10;
+
+#[derive(Debug, Clone)]
+pub enum SynthMessage {
+    AudioOverload,
+    /// A MIDI Program Change (`0xC0`) was received; `SynthApp` should load
+    /// whichever preset `settings.program_change_map` assigns to it.
+    ProgramChange(u8),
+    /// A raw SysEx message (`0xF0`..`0xF7`) was received; `SynthApp` hands
+    /// it to `SysExHandler` to check for an AudioTheorem preset dump.
+    SysEx(Vec<u8>),
+}
+
+/// A snapshot of UI-editable synth parameters, written by the UI thread and
+/// read by the audio thread via a `triple_buffer`. Kept `Copy`-free but
+/// cheap to clone so the UI can publish a fresh snapshot on every change
+/// without touching the audio thread's memory.
+#[derive(Debug, Clone)]
+pub struct SynthParameters {
+    pub oscillator_templates: Vec<Oscillator>,
+    pub a4_tuning_hz: f32,
+    pub retrigger_mode: RetriggerMode,
+    /// When set, incoming notes are snapped to the nearest tone in this
+    /// scale before they're sounded.
+    pub scale_quantize: Option<Scale>,
+    /// How note velocity stretches each voice's attack/release time.
+    pub velocity_scaling: VelocityScalingConfig,
+    /// Soft-clips the final mix through `tanh` before output when the
+    /// mix would otherwise exceed full scale.
+    pub auto_limiter_enabled: bool,
+    /// How this patch's oscillators combine into one output sample.
+    pub combination_mode: OscillatorCombinationMode,
+    /// Eight performance macro knobs, applied on top of the patch above by
+    /// the audio thread's `Synth::reapply_macros`.
+    pub macros: [MacroKnob; 8],
+    /// Normalized pitch-bend wheel position, -1.0 (full down) to 1.0 (full
+    /// up). The audio thread doesn't jump straight to this value; it slews
+    /// toward it over `Synth::PITCH_BEND_SLEW_MS` to avoid a zipper-noise
+    /// click on every wheel movement.
+    pub pitch_bend: f32,
+}
+
+/// Note on/off events sent from the UI (or MIDI) thread to the audio thread
+/// without ever blocking the audio callback. This lock-free `rtrb` queue is
+/// already the high-priority path `SynthMessage`/`SynthParameters` would
+/// need to be split from if they carried note timing: the audio callback
+/// drains it first, ahead of applying the (separately transported, single-
+/// most-recent-snapshot, never-queued) `SynthParameters`, so a flood of
+/// parameter changes can never delay a note. `SustainPedal` rides this same
+/// queue for the same reason.
+pub enum NoteEvent {
+    On(u8, u8),
+    Off(u8),
+    Panic,
+    /// Hard reset, distinct from `Panic`: also clears held-note tracking and
+    /// rebuilds every effect slot's DSP state, without touching parameters.
+    PanicReset,
+    SustainPedal(bool),
+}
+
+/// Whether `start_midi_clip_recording` clears `midi_clip_events` first or
+/// leaves them in place, for looping another recording pass on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MidiClipRecordMode {
+    #[default]
+    Overdub,
+    Replace,
+}
+
+/// Grid subdivision recorded note-on times can be snapped toward, as a
+/// fraction of a beat at `SynthApp::midi_clip_bpm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuantizeGrid {
+    Quarter,
+    Eighth,
+    #[default]
+    Sixteenth,
+}
+
+impl QuantizeGrid {
+    fn beats_per_step(self) -> f32 {
+        match self {
+            QuantizeGrid::Quarter => 1.0,
+            QuantizeGrid::Eighth => 0.5,
+            QuantizeGrid::Sixteenth => 0.25,
+        }
+    }
+}
+
+pub struct SynthApp {
+    parameter_producer: triple_buffer::Input<SynthParameters>,
+    note_producer: rtrb::Producer<NoteEvent>,
+    stream: Option<Stream>,
+    pub settings: AppSettings,
+    /// Set when the driver rejected `settings.buffer_size_frames` and the
+    /// stream fell back to the default size.
+    pub buffer_size_warning: Option<String>,
+    pub sample_rate: f32,
+    /// Rolling-average fraction (0.0-1.0) of the audio callback budget used,
+    /// encoded as `f32::to_bits` so the audio thread can update it without
+    /// a lock. Read it with `cpu_load()`.
+    cpu_load_bits: Arc<AtomicU32>,
+    pub message_receiver: std::sync::mpsc::Receiver<SynthMessage>,
+    pub keyboard_shortcuts: keyboard_shortcuts::KeyboardShortcuts,
+    waveform_consumer: triple_buffer::Output<Vec<f32>>,
+    pub analyzer: Analyzer,
+    pub ab_compare: ab_compare::AbCompare,
+    pub auto_save: autosave::AutoSave,
+    /// Shared with whichever MIDI input connection is active so its
+    /// callback thread can log messages the UI thread then reads.
+    pub midi_activity_log: Arc<Mutex<MidiActivityLog>>,
+    pub midi_log_filter: midi_log_filter::MidiLogFilter,
+    /// The preset library available to load, e.g. via the preset browser
+    /// or a MIDI Program Change through `settings.program_change_map`.
+    pub presets: Vec<SynthPreset>,
+    pub current_preset_name: Option<String>,
+    /// Audition clips from `SynthPreset::generate_preview`, keyed by preset
+    /// name, so re-opening the same preset's preview doesn't re-render it.
+    /// Cleared whenever `presets` is replaced via `set_presets`.
+    pub preset_preview_cache: HashMap<String, Vec<f32>>,
+    /// Shared with the audio thread's `Synth`; see
+    /// `Synth::osc_peak_levels`/`osc_clipped`. Read with `osc_peak_level`/
+    /// `osc_clipped`.
+    osc_peak_levels: [Arc<AtomicU32>; 3],
+    osc_clipped: [Arc<AtomicBool>; 3],
+    master_peak_level: Arc<AtomicU32>,
+    master_clipped: Arc<AtomicBool>,
+    /// Whether anything is actually reading `waveform_consumer` this frame
+    /// (set by `refresh_analyzer`, the only consumer). Shared with the audio
+    /// thread so it can skip publishing a waveform snapshot entirely when
+    /// nobody's watching, rather than copying `data` on every callback.
+    waveform_capture_enabled: Arc<AtomicBool>,
+    /// The loaded MIDI file driving sequencer playback/recording, if any.
+    pub midi_player: Option<MidiFilePlayer>,
+    /// Recorded (or hand-edited) parameter automation, fired by
+    /// `midi_player`'s tick position during playback.
+    pub automation_tracks: Vec<AutomationTrack>,
+    /// While true, `record_automation_point` appends to `automation_tracks`
+    /// at `midi_player`'s current tick.
+    pub recording_automation: bool,
+    /// The oscillator templates most recently published via
+    /// `push_parameters`, kept so `apply_automation` can mutate a
+    /// known-current copy rather than the audio thread's triple buffer.
+    current_oscillator_templates: Vec<Oscillator>,
+    current_a4_tuning_hz: f32,
+    current_retrigger_mode: RetriggerMode,
+    current_scale_quantize: Option<Scale>,
+    current_velocity_scaling: VelocityScalingConfig,
+    current_auto_limiter_enabled: bool,
+    current_combination_mode: OscillatorCombinationMode,
+    current_macros: [MacroKnob; 8],
+    current_pitch_bend: f32,
+    /// The external-input capture stream, if `settings.input_capture_enabled`.
+    /// Kept alive for as long as capture should run; dropping it stops
+    /// the stream.
+    input_stream: Option<Stream>,
+    /// Linear peak amplitude of the most recently captured input block.
+    input_peak_level: Arc<AtomicU32>,
+    /// `settings.input_mix`, mirrored here as bits so the audio thread can
+    /// read it without a lock.
+    input_mix_bits: Arc<AtomicU32>,
+    /// CC slew limiting and MIDI learn bindings. Shared with whichever MIDI
+    /// input connection is active, same as `midi_activity_log`.
+    pub midi_system: Arc<Mutex<MidiSystem>>,
+    /// The live hardware MIDI input connection opened by `connect_midi_input`,
+    /// if any. Held only to keep it alive; its callback runs on its own
+    /// thread and forwards messages through `midi_event_receiver`.
+    midi_input_connection: Option<midir::MidiInputConnection<()>>,
+    /// Receives `MidiMessage`s from `midi_input_connection`'s callback
+    /// thread. Drained by `poll_midi_input`, which should be called once
+    /// per frame alongside `advance_midi_playback`.
+    midi_event_receiver: Option<std::sync::mpsc::Receiver<MidiMessage>>,
+    /// Name of the currently connected input port, for display in
+    /// `ui::panels::midi_settings`. `None` when disconnected.
+    pub midi_input_port_name: Option<String>,
+    /// Set when `connect_midi_input` fails to open the requested port.
+    pub midi_input_error: Option<String>,
+    /// Last CC message received that had no MIDI-learn binding, smoothed
+    /// through `MidiSystem::apply_midi_cc`, as `(channel, controller,
+    /// smoothed_value)`. Display-only, for the MIDI settings panel.
+    pub last_unmapped_cc: Option<(u8, u8, f32)>,
+    /// When set, live input on MPE member channels (2-16) is treated as one
+    /// note per channel, with per-note pitch bend and pressure tracked in
+    /// `mpe_zone` instead of the channel-wide handling `handle_live_midi_message`
+    /// otherwise uses.
+    pub mpe_enabled: bool,
+    /// Per-note pitch bend and pressure for the active MPE zone.
+    pub mpe_zone: MpeZone,
+    /// The note currently sounding on each MPE member channel, so a
+    /// channel's `PitchBend`/`PolyphonicAftertouch` messages (which carry no
+    /// note number of their own) can be attributed to the right note.
+    mpe_active_notes: std::collections::HashMap<u8, u8>,
+    /// MIDI learn selector state for `ui::panels::midi_settings`.
+    pub midi_learn_channel: u8,
+    pub midi_learn_controller: u8,
+    pub midi_learn_target: MidiControlTarget,
+    /// Hex text box for `ui::panels::midi_settings`'s SysEx import field.
+    pub sysex_import_input: String,
+    /// While true, `note_on`/`note_off` append timed entries to
+    /// `midi_clip_events`, relative to `midi_clip_started_at`.
+    pub recording_midi_clip: bool,
+    midi_clip_started_at: Option<Instant>,
+    /// `(time_on, note, velocity, time_off)`, seconds from
+    /// `midi_clip_started_at`. `time_off` is `None` until the matching
+    /// `note_off` arrives.
+    midi_clip_events: Vec<(f32, u8, u8, Option<f32>)>,
+    /// Tempo used when writing `midi_clip_events` out as a MIDI file; this
+    /// app has no global sequencer BPM, so the clip exporter owns its own.
+    pub midi_clip_bpm: f32,
+    /// Whether `start_midi_clip_recording` clears `midi_clip_events` first
+    /// (`Replace`) or leaves existing events in place (`Overdub`).
+    pub midi_clip_record_mode: MidiClipRecordMode,
+    /// Grid subdivision new note-on times are snapped toward, in
+    /// `record_midi_clip_note_on`.
+    pub quantize_grid: QuantizeGrid,
+    /// How strongly note-on times are snapped to `quantize_grid`: `0.0`
+    /// leaves them untouched, `1.0` snaps fully onto the grid.
+    pub quantize_strength: f32,
+    /// Set by `copy_oscillator`, consumed by `paste_oscillator`. Holds a
+    /// full clone of one oscillator template for duplicating it onto
+    /// another.
+    pub oscillator_clipboard: Option<Oscillator>,
+    /// Timestamps of the last `tap_tempo` calls, oldest first, capped at 8.
+    pub tap_tempo_times: Vec<Instant>,
+    /// Octaves to shift `ui::panels::midi_settings`'s QWERTY-to-MIDI-note
+    /// reference table up or down from its default (A = C4).
+    pub keyboard_octave_offset: i8,
+    /// The wavetable edited by `ui::panels::wavetable_management`, loaded
+    /// either through that panel's Import button or by dropping a file
+    /// onto the window (see `handle_dropped_files`).
+    pub custom_wavetable: CustomWavetable,
+    /// Outcome of the last drag-and-drop wavetable load, for display
+    /// alongside the panel's own manual-import status.
+    pub wavetable_drop_status: Option<String>,
+    /// When true, `handle_dropped_files` calls `CustomWavetable::remove_dc_offset`
+    /// on every sample it loads, rather than leaving DC removal to the
+    /// "Remove DC Offset" button in the sample management panel.
+    pub auto_dc_removal: bool,
+    /// Markdown table from the most recent `run_benchmark` call, for
+    /// `ui::panels::audio_settings`'s "Run Benchmark" button to display.
+    pub benchmark_report: Option<String>,
+    /// A frozen copy of `analyzer.current_waveform_samples()`, captured by
+    /// `ui::panels::oscilloscope`'s "Hold" button so a waveform can stay on
+    /// screen for comparison while the live trace keeps moving. `None`
+    /// means the oscilloscope isn't held.
+    pub oscilloscope_hold: Option<Vec<f32>>,
+}
+
+impl Default for SynthApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SynthApp {
+    pub fn new() -> Self {
+        Self::with_settings(AppSettings::default())
+    }
+
+    pub fn with_settings(settings: AppSettings) -> Self {
+        let initial = SynthParameters {
+            oscillator_templates: vec![Oscillator::default()],
+            a4_tuning_hz: 440.0,
+            retrigger_mode: RetriggerMode::default(),
+            scale_quantize: None,
+            velocity_scaling: VelocityScalingConfig::default(),
+            auto_limiter_enabled: false,
+            combination_mode: OscillatorCombinationMode::default(),
+            macros: default_macros(),
+            pitch_bend: 0.0,
+        };
+        let (parameter_producer, parameter_consumer) = triple_buffer::TripleBuffer::new(&initial).split();
+        let (note_producer, note_consumer) = rtrb::RingBuffer::new(256);
+        let cpu_load_bits = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+        let (message_sender, message_receiver) = std::sync::mpsc::channel();
+        let (waveform_producer, waveform_consumer) = triple_buffer::TripleBuffer::new(&Vec::new()).split();
+        let osc_peak_levels: [Arc<AtomicU32>; 3] = std::array::from_fn(|_| Arc::new(AtomicU32::new(0.0f32.to_bits())));
+        let osc_clipped: [Arc<AtomicBool>; 3] = std::array::from_fn(|_| Arc::new(AtomicBool::new(false)));
+        let master_peak_level = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+        let master_clipped = Arc::new(AtomicBool::new(false));
+        let waveform_capture_enabled = Arc::new(AtomicBool::new(false));
+        let (input_producer, input_consumer) = rtrb::RingBuffer::new(4096);
+        let input_peak_level = Arc::new(AtomicU32::new(0.0f32.to_bits()));
+        let input_mix_bits = Arc::new(AtomicU32::new(settings.input_mix.to_bits()));
+
+        let (stream, buffer_size_warning, sample_rate) = match create_stream(CreateStreamConfig {
+            parameter_consumer,
+            note_consumer,
+            buffer_size_frames: settings.buffer_size_frames,
+            cpu_load_bits: cpu_load_bits.clone(),
+            message_sender,
+            waveform_producer,
+            waveform_capture_enabled: waveform_capture_enabled.clone(),
+            osc_peak_levels: osc_peak_levels.clone(),
+            osc_clipped: osc_clipped.clone(),
+            master_peak_level: master_peak_level.clone(),
+            master_clipped: master_clipped.clone(),
+            input_consumer,
+            input_mix_bits: input_mix_bits.clone(),
+        }) {
+            Ok((stream, fell_back, sample_rate)) => (
+                Some(stream),
+                fell_back.then(|| {
+                    "requested buffer size rejected by driver; using default".to_string()
+                }),
+                sample_rate,
+            ),
+            Err(_) => (None, None, 44_100.0),
+        };
+
+        let input_stream = settings
+            .input_capture_enabled
+            .then(|| create_input_stream(input_producer, input_peak_level.clone()).ok())
+            .flatten();
+
+        let auto_save = autosave::AutoSave::new(settings.auto_save_interval_minutes, recovery_file_path());
+
+        Self {
+            parameter_producer,
+            note_producer,
+            stream,
+            settings,
+            buffer_size_warning,
+            sample_rate,
+            cpu_load_bits,
+            message_receiver,
+            keyboard_shortcuts: keyboard_shortcuts::KeyboardShortcuts::default(),
+            waveform_consumer,
+            waveform_capture_enabled,
+            analyzer: Analyzer::default(),
+            ab_compare: ab_compare::AbCompare::default(),
+            auto_save,
+            midi_activity_log: Arc::new(Mutex::new(MidiActivityLog::default())),
+            midi_log_filter: midi_log_filter::MidiLogFilter::default(),
+            presets: Vec::new(),
+            current_preset_name: None,
+            preset_preview_cache: HashMap::new(),
+            osc_peak_levels,
+            osc_clipped,
+            master_peak_level,
+            master_clipped,
+            midi_player: None,
+            automation_tracks: Vec::new(),
+            recording_automation: false,
+            current_oscillator_templates: initial.oscillator_templates,
+            current_a4_tuning_hz: initial.a4_tuning_hz,
+            current_retrigger_mode: initial.retrigger_mode,
+            current_scale_quantize: initial.scale_quantize,
+            current_velocity_scaling: initial.velocity_scaling,
+            current_auto_limiter_enabled: initial.auto_limiter_enabled,
+            current_combination_mode: initial.combination_mode,
+            current_macros: initial.macros,
+            current_pitch_bend: initial.pitch_bend,
+            input_stream,
+            input_peak_level,
+            input_mix_bits,
+            midi_system: Arc::new(Mutex::new(MidiSystem::new(0.1))),
+            midi_input_connection: None,
+            midi_event_receiver: None,
+            midi_input_port_name: None,
+            midi_input_error: None,
+            last_unmapped_cc: None,
+            mpe_enabled: false,
+            mpe_zone: MpeZone::new(),
+            mpe_active_notes: std::collections::HashMap::new(),
+            midi_learn_channel: 0,
+            midi_learn_controller: 0,
+            midi_learn_target: MidiControlTarget::MasterVolume,
+            sysex_import_input: String::new(),
+            recording_midi_clip: false,
+            midi_clip_started_at: None,
+            midi_clip_events: Vec::new(),
+            midi_clip_bpm: 120.0,
+            midi_clip_record_mode: MidiClipRecordMode::default(),
+            quantize_grid: QuantizeGrid::default(),
+            quantize_strength: 0.0,
+            oscillator_clipboard: None,
+            tap_tempo_times: Vec::new(),
+            keyboard_octave_offset: 0,
+            custom_wavetable: CustomWavetable::default(),
+            wavetable_drop_status: None,
+            auto_dc_removal: false,
+            benchmark_report: None,
+            oscilloscope_hold: None,
+        }
+    }
+
+    /// Records a tap and, once at least 2 taps have been recorded, updates
+    /// `midi_clip_bpm` from the average interval between the last 4 taps
+    /// (or however many are available below that). There's no dedicated
+    /// sequencer/arpeggiator tempo in this app, so `midi_clip_bpm` is the
+    /// one real BPM setting tap tempo can drive.
+    pub fn tap_tempo(&mut self) {
+        self.tap_tempo_times.push(Instant::now());
+        if self.tap_tempo_times.len() > 8 {
+            self.tap_tempo_times.remove(0);
+        }
+        if self.tap_tempo_times.len() < 2 {
+            return;
+        }
+        let recent = &self.tap_tempo_times[self.tap_tempo_times.len().saturating_sub(4)..];
+        let intervals: Vec<f32> = recent.windows(2).map(|pair| (pair[1] - pair[0]).as_secs_f32()).collect();
+        let average_interval = intervals.iter().sum::<f32>() / intervals.len() as f32;
+        if average_interval > 0.0 {
+            self.midi_clip_bpm = 60.0 / average_interval;
+        }
+    }
+
+    /// True while one or more files are being dragged over the window, so
+    /// the custom wavetable section can highlight itself as a drop target.
+    pub fn files_hovering(&self, ctx: &egui::Context) -> bool {
+        ctx.input(|input| !input.raw.hovered_files.is_empty())
+    }
+
+    /// Loads every dropped `.wav`/`.aiff`/`.aif` file, in drop order, into
+    /// `custom_wavetable`. Later files overwrite earlier ones, since
+    /// `custom_wavetable` holds a single table; `wavetable_drop_status`
+    /// reports the outcome of the last file processed.
+    pub fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped_files = ctx.input(|input| input.raw.dropped_files.clone());
+        for dropped_file in dropped_files {
+            let Some(path) = dropped_file.path else { continue };
+            let is_sample_file = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(|extension| extension.eq_ignore_ascii_case("wav") || extension.eq_ignore_ascii_case("aiff") || extension.eq_ignore_ascii_case("aif"))
+                .unwrap_or(false);
+            if !is_sample_file {
+                continue;
+            }
+            match load_sample(&path, self.sample_rate as u32, ChannelConversion::MixDown) {
+                Ok((mut wavetable, report)) => {
+                    if self.auto_dc_removal {
+                        wavetable.remove_dc_offset();
+                    }
+                    self.custom_wavetable = wavetable;
+                    self.wavetable_drop_status = Some(format!(
+                        "Loaded {:?} ({} Hz{})",
+                        path.file_name().unwrap_or_default(),
+                        report.loaded_sample_rate,
+                        if report.needs_resample { ", rate mismatch — will play mistuned" } else { "" },
+                    ));
+                }
+                Err(error) => {
+                    self.wavetable_drop_status = Some(format!("Failed to load {:?}: {error}", path.file_name().unwrap_or_default()));
+                }
+            }
+        }
+    }
+
+    /// The oscillator templates most recently published to the audio
+    /// thread, for panels that only need to read them (e.g. to draw a
+    /// routing diagram) rather than mutate one in place.
+    pub fn current_oscillator_templates(&self) -> &[Oscillator] {
+        &self.current_oscillator_templates
+    }
+
+    /// Stores a clone of oscillator `index` in `oscillator_clipboard`.
+    pub fn copy_oscillator(&mut self, index: usize) {
+        if let Some(oscillator) = self.current_oscillator_templates.get(index) {
+            self.oscillator_clipboard = Some(oscillator.clone());
+        }
+    }
+
+    /// Overwrites oscillator `index` with the clipboard's contents, if any,
+    /// and republishes the patch.
+    pub fn paste_oscillator(&mut self, index: usize) {
+        let Some(clipboard) = self.oscillator_clipboard.clone() else { return };
+        let mut oscillator_templates = self.current_oscillator_templates.clone();
+        let Some(slot) = oscillator_templates.get_mut(index) else { return };
+        *slot = clipboard;
+        self.push_parameters(SynthParameters {
+            oscillator_templates,
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Exchanges the entire configuration of oscillators `a` and `b` and
+    /// republishes the patch.
+    pub fn swap_oscillators(&mut self, a: usize, b: usize) {
+        let mut oscillator_templates = self.current_oscillator_templates.clone();
+        if a >= oscillator_templates.len() || b >= oscillator_templates.len() {
+            return;
+        }
+        oscillator_templates.swap(a, b);
+        self.push_parameters(SynthParameters {
+            oscillator_templates,
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Starts a MIDI clip recording. In `MidiClipRecordMode::Replace`, any
+    /// previously captured events are discarded first; in `Overdub`, new
+    /// notes are appended alongside them.
+    pub fn start_midi_clip_recording(&mut self) {
+        if self.midi_clip_record_mode == MidiClipRecordMode::Replace {
+            self.midi_clip_events.clear();
+        }
+        self.midi_clip_started_at = Some(Instant::now());
+        self.recording_midi_clip = true;
+    }
+
+    /// Snaps `time_seconds` toward the nearest `quantize_grid` line at
+    /// `midi_clip_bpm`, blended by `quantize_strength` (`0.0` leaves it
+    /// untouched, `1.0` snaps fully onto the grid).
+    fn quantize_time(&self, time_seconds: f32) -> f32 {
+        let seconds_per_step = 60.0 / self.midi_clip_bpm.max(1.0) * self.quantize_grid.beats_per_step();
+        let nearest_step = (time_seconds / seconds_per_step).round() * seconds_per_step;
+        time_seconds + (nearest_step - time_seconds) * self.quantize_strength.clamp(0.0, 1.0)
+    }
+
+    /// Stops recording without discarding the captured events, so they can
+    /// still be exported afterwards.
+    pub fn stop_midi_clip_recording(&mut self) {
+        self.recording_midi_clip = false;
+    }
+
+    /// Writes the captured `midi_clip_events` out as a Type 0 Standard MIDI
+    /// File at `midi_clip_bpm`, for dragging into a DAW. Any note still
+    /// missing a `time_off` (recording stopped while held) is closed at the
+    /// clip's last event time.
+    pub fn export_midi_clip(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        const TICKS_PER_QUARTER: u16 = 480;
+        let microseconds_per_quarter = (60_000_000.0 / self.midi_clip_bpm.max(1.0)) as u32;
+        let end_time = self.midi_clip_events.iter().map(|(on, _, _, off)| off.unwrap_or(*on)).fold(0.0f32, f32::max);
+
+        let mut raw_events: Vec<(f32, midly::TrackEventKind<'static>)> = Vec::new();
+        for &(time_on, note, velocity, time_off) in &self.midi_clip_events {
+            raw_events.push((
+                time_on,
+                midly::TrackEventKind::Midi {
+                    channel: 0.into(),
+                    message: midly::MidiMessage::NoteOn { key: note.into(), vel: velocity.into() },
+                },
+            ));
+            raw_events.push((
+                time_off.unwrap_or(end_time),
+                midly::TrackEventKind::Midi {
+                    channel: 0.into(),
+                    message: midly::MidiMessage::NoteOff { key: note.into(), vel: 0.into() },
+                },
+            ));
+        }
+        raw_events.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+        let seconds_per_tick = (microseconds_per_quarter as f64 / 1_000_000.0) / TICKS_PER_QUARTER as f64;
+        let mut track = midly::Track::new();
+        track.push(midly::TrackEvent {
+            delta: 0.into(),
+            kind: midly::TrackEventKind::Meta(midly::MetaMessage::Tempo(microseconds_per_quarter.into())),
+        });
+        let mut last_tick = 0u64;
+        for (time, kind) in raw_events {
+            let tick = (time as f64 / seconds_per_tick) as u64;
+            track.push(midly::TrackEvent { delta: ((tick - last_tick) as u32).into(), kind });
+            last_tick = tick;
+        }
+        track.push(midly::TrackEvent { delta: 0.into(), kind: midly::TrackEventKind::Meta(midly::MetaMessage::EndOfTrack) });
+
+        let smf = midly::Smf {
+            header: midly::Header::new(midly::Format::SingleTrack, midly::Timing::Metrical(TICKS_PER_QUARTER.into())),
+            tracks: vec![track],
+        };
+        smf.save(path)?;
+        Ok(())
+    }
+
+    /// Records `note_on`/`note_off` into the active MIDI clip, if one is
+    /// being recorded. No-op otherwise.
+    fn record_midi_clip_note_on(&mut self, note: u8, velocity: u8) {
+        if !self.recording_midi_clip {
+            return;
+        }
+        let Some(started_at) = self.midi_clip_started_at else { return };
+        let time_on = self.quantize_time(started_at.elapsed().as_secs_f32());
+        self.midi_clip_events.push((time_on, note, velocity, None));
+    }
+
+    fn record_midi_clip_note_off(&mut self, note: u8) {
+        if !self.recording_midi_clip {
+            return;
+        }
+        let Some(started_at) = self.midi_clip_started_at else { return };
+        let time_off = started_at.elapsed().as_secs_f32();
+        if let Some(event) = self.midi_clip_events.iter_mut().rev().find(|(_, n, _, off)| *n == note && off.is_none()) {
+            event.3 = Some(time_off);
+        }
+    }
+
+    /// Encodes the current patch as an AudioTheorem bulk preset dump SysEx
+    /// message, for display/copy (or, with a real MIDI output connection,
+    /// sending to compatible hardware).
+    pub fn encode_current_preset_sysex(&self) -> Vec<u8> {
+        let transient = SynthPreset::new(
+            String::new(),
+            self.current_oscillator_templates.clone(),
+            String::new(),
+            String::new(),
+            Vec::new(),
+            String::new(),
+        );
+        SysExHandler::encode_preset_dump(&transient)
+    }
+
+    /// Decodes `data` as an AudioTheorem bulk preset dump and, if it is
+    /// one, loads its oscillator templates into the current patch.
+    /// Returns whether a preset was applied.
+    pub fn apply_sysex_message(&mut self, data: &[u8]) -> bool {
+        let Some(preset) = SysExHandler::handle(data) else {
+            return false;
+        };
+        self.push_parameters(SynthParameters {
+            oscillator_templates: preset.oscillator_templates,
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+        true
+    }
+
+    /// Replaces the preset library and evicts `preset_preview_cache`, since
+    /// a cached preview keyed by name could otherwise go stale if a preset
+    /// by that name is re-synced with different oscillator templates.
+    pub fn set_presets(&mut self, presets: Vec<SynthPreset>) {
+        self.presets = presets;
+        self.preset_preview_cache.clear();
+    }
+
+    /// Returns a cached audition clip for the preset named `name`, rendering
+    /// and caching one via `SynthPreset::generate_preview` on first request.
+    /// Returns `None` if no preset by that name exists.
+    pub fn preset_preview(&mut self, name: &str) -> Option<&[f32]> {
+        if !self.preset_preview_cache.contains_key(name) {
+            let preset = self.presets.iter().find(|preset| preset.name == name)?;
+            let preview = preset.generate_preview(self.sample_rate);
+            self.preset_preview_cache.insert(name.to_string(), preview);
+        }
+        self.preset_preview_cache.get(name).map(Vec::as_slice)
+    }
+
+    /// Loads the preset named `name` from `presets`, if present, publishing
+    /// its oscillator templates to the audio thread.
+    pub fn load_preset_by_name(&mut self, name: &str) {
+        let Some(preset) = self.presets.iter().find(|preset| preset.name == name) else {
+            return;
+        };
+        self.current_preset_name = Some(preset.name.clone());
+        self.push_parameters(SynthParameters {
+            oscillator_templates: preset.oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Applies `SynthPreset::mutate` to the currently active patch (loaded
+    /// preset or not) with a fresh random seed, and publishes the result.
+    /// Repeated presses produce different variations since the seed is
+    /// never reused.
+    pub fn mutate_current_patch(&mut self, mutation_rate: f32) {
+        let transient = SynthPreset::new(
+            String::new(),
+            self.current_oscillator_templates.clone(),
+            String::new(),
+            String::new(),
+            Vec::new(),
+            String::new(),
+        );
+        let seed = rand::random::<u64>();
+        let mutated = transient.mutate(mutation_rate, seed);
+        self.push_parameters(SynthParameters {
+            oscillator_templates: mutated.oscillator_templates,
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Loads whichever preset `settings.program_change_map` assigns to
+    /// `program`, if any. No-op for unassigned program numbers.
+    pub fn handle_program_change(&mut self, program: u8) {
+        if let Some(name) = self.settings.program_change_map.get(&program).cloned() {
+            self.load_preset_by_name(&name);
+        }
+    }
+
+    /// Fills program numbers `0..presets.len()` with `presets`, sorted
+    /// alphabetically by name, overwriting any existing assignments.
+    pub fn auto_assign_program_changes(&mut self) {
+        let mut names: Vec<String> = self.presets.iter().map(|preset| preset.name.clone()).collect();
+        names.sort();
+        self.settings.program_change_map.clear();
+        for (program, name) in names.into_iter().enumerate().take(128) {
+            self.settings.program_change_map.insert(program as u8, name);
+        }
+    }
+
+    /// Pulls the latest audio-thread buffer into `analyzer`. Call this once
+    /// per UI frame before drawing an oscilloscope or spectrum view.
+    pub fn refresh_analyzer(&mut self) {
+        self.waveform_capture_enabled.store(true, Ordering::Relaxed);
+        let latest = self.waveform_consumer.read().clone();
+        if !latest.is_empty() {
+            self.analyzer.push_samples(&latest);
+        }
+    }
+
+    /// Applies any keyboard shortcuts triggered this frame.
+    pub fn handle_keyboard_shortcuts(&mut self, ctx: &egui::Context) {
+        for action in self.keyboard_shortcuts.triggered_actions(ctx) {
+            match action {
+                keyboard_shortcuts::ShortcutAction::PanicAllNotesOff => self.panic_all_notes_off(),
+                keyboard_shortcuts::ShortcutAction::TapTempo => self.tap_tempo(),
+                // The remaining actions are wired up by the panels that own
+                // playback/preset/focus state.
+                _ => {}
+            }
+        }
+    }
+
+    /// Rolling-average fraction (0.0-1.0) of the audio callback budget
+    /// consumed over the last `CPU_LOAD_WINDOW` callbacks.
+    pub fn cpu_load(&self) -> f32 {
+        f32::from_bits(self.cpu_load_bits.load(Ordering::Relaxed))
+    }
+
+    /// Linear peak amplitude (0.0+, 1.0 = 0 dBFS) for oscillator template
+    /// `index` (0-2), decaying toward silence once the audio thread stops
+    /// updating it. Out-of-range indices read as silence.
+    pub fn osc_peak_level(&self, index: usize) -> f32 {
+        self.osc_peak_levels
+            .get(index)
+            .map(|level| f32::from_bits(level.load(Ordering::Relaxed)))
+            .unwrap_or(0.0)
+    }
+
+    /// Whether oscillator template `index` has clipped since it was last
+    /// reset with `reset_osc_clip`.
+    pub fn osc_clipped(&self, index: usize) -> bool {
+        self.osc_clipped
+            .get(index)
+            .map(|clipped| clipped.load(Ordering::Relaxed))
+            .unwrap_or(false)
+    }
+
+    /// Clears the clip latch for oscillator template `index`, e.g. when the
+    /// user clicks its meter's clip indicator.
+    pub fn reset_osc_clip(&self, index: usize) {
+        if let Some(clipped) = self.osc_clipped.get(index) {
+            clipped.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Linear peak amplitude of the final output mix.
+    pub fn master_peak_level(&self) -> f32 {
+        f32::from_bits(self.master_peak_level.load(Ordering::Relaxed))
+    }
+
+    /// Whether the master mix has clipped since it was last reset.
+    pub fn master_clipped(&self) -> bool {
+        self.master_clipped.load(Ordering::Relaxed)
+    }
+
+    /// Clears the master clip latch.
+    pub fn reset_master_clip(&self) {
+        self.master_clipped.store(false, Ordering::Relaxed);
+    }
+
+    /// Hot-reloads the audio stream with the current `settings`, e.g. after
+    /// the user changes `buffer_size_frames` in the audio settings panel.
+    pub fn change_audio_devices(&mut self) {
+        self.stream = None;
+        let initial = SynthParameters {
+            oscillator_templates: vec![Oscillator::default()],
+            a4_tuning_hz: 440.0,
+            retrigger_mode: RetriggerMode::default(),
+            scale_quantize: None,
+            velocity_scaling: VelocityScalingConfig::default(),
+            auto_limiter_enabled: false,
+            combination_mode: OscillatorCombinationMode::default(),
+            macros: default_macros(),
+            pitch_bend: 0.0,
+        };
+        let (parameter_producer, parameter_consumer) = triple_buffer::TripleBuffer::new(&initial).split();
+        let (note_producer, note_consumer) = rtrb::RingBuffer::new(256);
+        let (message_sender, message_receiver) = std::sync::mpsc::channel();
+        let (waveform_producer, waveform_consumer) = triple_buffer::TripleBuffer::new(&Vec::new()).split();
+        let (input_producer, input_consumer) = rtrb::RingBuffer::new(4096);
+
+        match create_stream(CreateStreamConfig {
+            parameter_consumer,
+            note_consumer,
+            buffer_size_frames: self.settings.buffer_size_frames,
+            cpu_load_bits: self.cpu_load_bits.clone(),
+            message_sender,
+            waveform_producer,
+            waveform_capture_enabled: self.waveform_capture_enabled.clone(),
+            osc_peak_levels: self.osc_peak_levels.clone(),
+            osc_clipped: self.osc_clipped.clone(),
+            master_peak_level: self.master_peak_level.clone(),
+            master_clipped: self.master_clipped.clone(),
+            input_consumer,
+            input_mix_bits: self.input_mix_bits.clone(),
+        }) {
+            Ok((stream, fell_back, sample_rate)) => {
+                self.parameter_producer = parameter_producer;
+                self.note_producer = note_producer;
+                self.stream = Some(stream);
+                self.sample_rate = sample_rate;
+                self.message_receiver = message_receiver;
+                self.waveform_consumer = waveform_consumer;
+                self.buffer_size_warning = fell_back.then(|| {
+                    "requested buffer size rejected by driver; using default".to_string()
+                });
+                // The old input stream's producer half no longer has a
+                // matching consumer; rebuild it too if capture is enabled.
+                self.input_stream = self
+                    .settings
+                    .input_capture_enabled
+                    .then(|| create_input_stream(input_producer, self.input_peak_level.clone()).ok())
+                    .flatten();
+            }
+            Err(err) => {
+                self.buffer_size_warning = Some(format!("failed to open audio stream: {err}"));
+            }
+        }
+    }
+
+    /// Enables or disables external-input capture, opening or tearing down
+    /// the input stream as needed and persisting the choice in `settings`.
+    pub fn set_input_capture_enabled(&mut self, enabled: bool) {
+        self.settings.input_capture_enabled = enabled;
+        self.change_audio_devices();
+    }
+
+    /// Sets the linear gain applied to captured input before it's mixed
+    /// into the synth's signal path.
+    pub fn set_input_mix(&mut self, input_mix: f32) {
+        self.settings.input_mix = input_mix;
+        self.input_mix_bits.store(input_mix.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Linear peak amplitude of the most recently captured input block.
+    pub fn input_peak_level(&self) -> f32 {
+        f32::from_bits(self.input_peak_level.load(Ordering::Relaxed))
+    }
+
+    /// Publishes a new parameter snapshot for the audio thread to pick up
+    /// on its next callback. Never blocks.
+    pub fn push_parameters(&mut self, parameters: SynthParameters) {
+        self.current_oscillator_templates = parameters.oscillator_templates.clone();
+        self.current_a4_tuning_hz = parameters.a4_tuning_hz;
+        self.current_retrigger_mode = parameters.retrigger_mode;
+        self.current_scale_quantize = parameters.scale_quantize.clone();
+        self.current_velocity_scaling = parameters.velocity_scaling;
+        self.current_auto_limiter_enabled = parameters.auto_limiter_enabled;
+        self.current_combination_mode = parameters.combination_mode.clone();
+        self.current_macros = parameters.macros.clone();
+        self.current_pitch_bend = parameters.pitch_bend;
+        self.parameter_producer.write(parameters);
+    }
+
+    /// Whether the auto-limiter is currently enabled.
+    pub fn current_auto_limiter_enabled(&self) -> bool {
+        self.current_auto_limiter_enabled
+    }
+
+    /// How this patch's oscillators are currently combined, most recently
+    /// published to the audio thread.
+    pub fn current_combination_mode(&self) -> &OscillatorCombinationMode {
+        &self.current_combination_mode
+    }
+
+    /// Sets how this patch's oscillators combine into one output sample,
+    /// and republishes the current patch with it applied.
+    pub fn set_combination_mode(&mut self, combination_mode: OscillatorCombinationMode) {
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode,
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// The eight performance macro knobs most recently published to the
+    /// audio thread.
+    pub fn current_macros(&self) -> &[MacroKnob; 8] {
+        &self.current_macros
+    }
+
+    /// Sets macro `index`'s value (clamped to `0.0..1.0`) and republishes
+    /// the current patch with it applied. `Synth::reapply_macros` is what
+    /// actually evaluates `routes` against the new value, on the audio
+    /// thread, once this snapshot lands.
+    pub fn set_macro_value(&mut self, index: usize, value: f32) {
+        if let Some(knob) = self.current_macros.get_mut(index) {
+            knob.value = value.clamp(0.0, 1.0);
+        }
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Normalized pitch-bend wheel position most recently published to the
+    /// audio thread, -1.0 to 1.0.
+    pub fn current_pitch_bend(&self) -> f32 {
+        self.current_pitch_bend
+    }
+
+    /// Sets the pitch-bend wheel position (clamped to `-1.0..=1.0`) and
+    /// republishes the current patch with it applied. The audio thread
+    /// slews toward this rather than jumping straight to it; see
+    /// `Synth::pitch_bend`.
+    pub fn set_pitch_bend(&mut self, pitch_bend: f32) {
+        self.current_pitch_bend = pitch_bend.clamp(-1.0, 1.0);
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Routes `target` through macro `index`, replacing any existing route
+    /// to the same target on that knob, for "Assign to Macro N" UI actions.
+    /// Republishes the current patch with it applied.
+    pub fn assign_macro_route(&mut self, index: usize, route: MacroRoute) {
+        if let Some(knob) = self.current_macros.get_mut(index) {
+            knob.routes.retain(|existing| existing.target != route.target);
+            knob.routes.push(route);
+        }
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Toggles soft-clipping the final mix to tame overs, and republishes
+    /// the current patch with it applied.
+    pub fn set_auto_limiter_enabled(&mut self, auto_limiter_enabled: bool) {
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// The retrigger mode most recently published to the audio thread.
+    pub fn current_retrigger_mode(&self) -> RetriggerMode {
+        self.current_retrigger_mode
+    }
+
+    /// Sets how retriggering an already-sounding note behaves (see
+    /// `RetriggerMode`) and republishes the current patch with it applied.
+    pub fn set_retrigger_mode(&mut self, retrigger_mode: RetriggerMode) {
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// The scale lock most recently published to the audio thread, if any.
+    pub fn current_scale_quantize(&self) -> Option<&Scale> {
+        self.current_scale_quantize.as_ref()
+    }
+
+    /// Sets (or clears, with `None`) scale-lock quantization and
+    /// republishes the current patch with it applied.
+    pub fn set_scale_quantize(&mut self, scale_quantize: Option<Scale>) {
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize,
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// The velocity-to-attack/release scaling most recently published to
+    /// the audio thread.
+    pub fn current_velocity_scaling(&self) -> VelocityScalingConfig {
+        self.current_velocity_scaling
+    }
+
+    /// Sets velocity-to-attack/release scaling and republishes the current
+    /// patch with it applied.
+    pub fn set_velocity_scaling(&mut self, velocity_scaling: VelocityScalingConfig) {
+        self.push_parameters(SynthParameters {
+            oscillator_templates: self.current_oscillator_templates.clone(),
+            a4_tuning_hz: self.current_a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    /// Loads a Standard MIDI File into the sequencer for the next
+    /// `advance_midi_playback`/recording session, replacing any tracks
+    /// already loaded. Automation recorded so far is kept as-is; call
+    /// `set_automation_tracks` after loading if it should follow the new
+    /// file's timeline.
+    pub fn load_midi_file(&mut self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut player = MidiFilePlayer::load(path)?;
+        player.set_automation_tracks(self.automation_tracks.clone());
+        self.midi_player = Some(player);
+        Ok(())
+    }
+
+    /// Records `value` for `parameter` at `midi_player`'s current tick, if
+    /// `recording_automation` is set, a file is loaded, and `parameter`'s
+    /// track (if it already has one) is enabled. No-op otherwise.
+    pub fn record_automation_point(&mut self, parameter: AutomationParameter, value: f32) {
+        if !self.recording_automation {
+            return;
+        }
+        let Some(player) = &self.midi_player else { return };
+        let tick = player.current_tick;
+        match self.automation_tracks.iter_mut().find(|track| track.parameter == parameter) {
+            Some(track) => {
+                if track.enabled {
+                    track.record(tick, value);
+                }
+            }
+            None => {
+                let mut track = AutomationTrack::new(parameter);
+                track.record(tick, value);
+                self.automation_tracks.push(track);
+            }
+        }
+        if let Some(player) = &mut self.midi_player {
+            player.set_automation_tracks(self.automation_tracks.clone());
+        }
+    }
+
+    /// Advances `midi_player` by `dt_seconds`, playing its note events and
+    /// firing any recorded automation. No-op if no file is loaded.
+    pub fn advance_midi_playback(&mut self, dt_seconds: f32) {
+        let Some(mut player) = self.midi_player.take() else { return };
+        let mut fired = Vec::new();
+        player.tick(dt_seconds, |event| fired.push(event));
+        self.midi_player = Some(player);
+
+        for event in fired {
+            match event {
+                PlaybackEvent::NoteOn { note, velocity, .. } => self.note_on(note, velocity),
+                PlaybackEvent::NoteOff { note, .. } => self.note_off(note),
+                PlaybackEvent::ControlChange { .. } => {}
+                PlaybackEvent::Automation { parameter, value } => self.apply_automation(parameter, value),
+            }
+        }
+    }
+
+    /// Lists the names of the currently available MIDI input ports, for
+    /// the port selector in `ui::panels::midi_settings`.
+    pub fn available_midi_input_ports() -> Vec<String> {
+        input::available_ports()
+    }
+
+    /// Opens a live MIDI input connection on `port_name`, replacing any
+    /// existing connection. Notes and CCs received over it are routed the
+    /// same way MIDI file playback and the on-screen keyboard are, via
+    /// `poll_midi_input`.
+    pub fn connect_midi_input(&mut self, port_name: &str) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        match input::connect(port_name, move |message| {
+            let _ = sender.send(message);
+        }) {
+            Ok(connection) => {
+                self.midi_input_connection = Some(connection);
+                self.midi_event_receiver = Some(receiver);
+                self.midi_input_port_name = Some(port_name.to_string());
+                self.midi_input_error = None;
+            }
+            Err(err) => {
+                self.midi_input_error = Some(format!("failed to connect to \"{port_name}\": {err}"));
+            }
+        }
+    }
+
+    /// Closes the live MIDI input connection, if any.
+    pub fn disconnect_midi_input(&mut self) {
+        self.midi_input_connection = None;
+        self.midi_event_receiver = None;
+        self.midi_input_port_name = None;
+    }
+
+    /// Drains messages received from the live MIDI input connection (if
+    /// any), logging each to `midi_activity_log` and routing notes/CCs the
+    /// same way `note_on`/`note_off`/MIDI learn do. Intended to be polled
+    /// once per frame, alongside `advance_midi_playback`.
+    pub fn poll_midi_input(&mut self) {
+        let Some(receiver) = &self.midi_event_receiver else { return };
+        let messages: Vec<MidiMessage> = receiver.try_iter().collect();
+        for message in messages {
+            self.handle_live_midi_message(message);
+        }
+    }
+
+    fn handle_live_midi_message(&mut self, message: MidiMessage) {
+        match message {
+            MidiMessage::NoteOn { channel, note, velocity } => {
+                self.midi_activity_log.lock().unwrap().push(MidiMessageType::NoteOn, channel, [note, velocity, 0]);
+                if self.mpe_enabled && channel != 0 {
+                    self.mpe_active_notes.insert(channel, note);
+                }
+                self.note_on(note, velocity);
+            }
+            MidiMessage::NoteOff { channel, note } => {
+                self.midi_activity_log.lock().unwrap().push(MidiMessageType::NoteOff, channel, [note, 0, 0]);
+                if self.mpe_enabled && channel != 0 {
+                    self.mpe_active_notes.remove(&channel);
+                    self.mpe_zone.clear_note(channel, note);
+                }
+                self.note_off(note);
+            }
+            MidiMessage::ControlChange { channel, controller, value } => {
+                self.midi_activity_log.lock().unwrap().push(MidiMessageType::ControlChange, channel, [controller, value, 0]);
+                let resolved = self.midi_system.lock().unwrap().resolve_cc(channel, controller, value);
+                if let Some((target, resolved)) = resolved {
+                    if let MidiControlTarget::Macro(index) = target {
+                        self.set_macro_value(index, resolved);
+                    }
+                } else {
+                    let smoothed = self.midi_system.lock().unwrap().apply_midi_cc(channel, controller, value);
+                    self.last_unmapped_cc = Some((channel, controller, smoothed));
+                }
+            }
+            MidiMessage::PitchBend { channel, value } => {
+                self.midi_activity_log.lock().unwrap().push(
+                    MidiMessageType::PitchBend,
+                    channel,
+                    [(value >> 7) as u8, (value & 0x7F) as u8, 0],
+                );
+                if self.mpe_enabled && channel != 0 {
+                    if let Some(&note) = self.mpe_active_notes.get(&channel) {
+                        self.mpe_zone.set_pitch_bend(channel, note, value);
+                        self.sync_mpe_pitch_bend(channel, note);
+                    }
+                }
+            }
+            MidiMessage::PolyphonicAftertouch { channel, note, pressure } => {
+                self.midi_activity_log.lock().unwrap().push(MidiMessageType::PolyphonicAftertouch, channel, [note, pressure, 0]);
+                if self.mpe_enabled && channel != 0 {
+                    self.mpe_zone.set_pressure(channel, note, pressure);
+                }
+            }
+            MidiMessage::SysEx(bytes) => {
+                self.apply_sysex_message(&bytes);
+            }
+        }
+    }
+
+    /// Approximates this MPE note's per-note pitch bend by pushing it
+    /// through the synth's one existing pitch-bend lane (`set_pitch_bend`),
+    /// scaled from the zone's wide bend range down to
+    /// `PITCH_BEND_RANGE_SEMITONES`. This is not true per-note pitch: with
+    /// more than one MPE note held, every voice bends together. `Voice` has
+    /// no per-voice pitch lane to do better without a larger synth change.
+    fn sync_mpe_pitch_bend(&mut self, channel: u8, note: u8) {
+        let semitones = self.mpe_zone.state_for_note(channel, note).pitch_bend_semitones;
+        self.set_pitch_bend(semitones / crate::core::synth::PITCH_BEND_RANGE_SEMITONES);
+    }
+
+    /// Applies a single automation point to the current patch and
+    /// republishes it, mirroring whatever a user dragging the matching
+    /// slider would have done.
+    fn apply_automation(&mut self, parameter: AutomationParameter, value: f32) {
+        let mut oscillator_templates = self.current_oscillator_templates.clone();
+        let mut a4_tuning_hz = self.current_a4_tuning_hz;
+        match parameter {
+            AutomationParameter::OscillatorVolume(index) => {
+                if let Some(oscillator) = oscillator_templates.get_mut(index) {
+                    oscillator.volume = value;
+                }
+            }
+            AutomationParameter::OscillatorPan(index) => {
+                if let Some(oscillator) = oscillator_templates.get_mut(index) {
+                    oscillator.pan = value;
+                }
+            }
+            AutomationParameter::OscillatorFilterCutoff(index) => {
+                if let Some(oscillator) = oscillator_templates.get_mut(index) {
+                    oscillator.filter.cutoff_hz = value;
+                }
+            }
+            AutomationParameter::A4TuningHz => a4_tuning_hz = value,
+        }
+        self.push_parameters(SynthParameters {
+            oscillator_templates,
+            a4_tuning_hz,
+            retrigger_mode: self.current_retrigger_mode,
+            scale_quantize: self.current_scale_quantize.clone(),
+            velocity_scaling: self.current_velocity_scaling,
+            auto_limiter_enabled: self.current_auto_limiter_enabled,
+            combination_mode: self.current_combination_mode.clone(),
+            macros: self.current_macros.clone(),
+            pitch_bend: self.current_pitch_bend,
+        });
+    }
+
+    pub fn note_on(&mut self, note: u8, velocity: u8) {
+        self.record_midi_clip_note_on(note, velocity);
+        let _ = self.note_producer.push(NoteEvent::On(note, velocity));
+    }
+
+    pub fn note_off(&mut self, note: u8) {
+        self.record_midi_clip_note_off(note);
+        let _ = self.note_producer.push(NoteEvent::Off(note));
+    }
+
+    /// Sends a MIDI panic event: the audio thread drops every active voice
+    /// on its next callback.
+    pub fn panic_all_notes_off(&mut self) {
+        let _ = self.note_producer.push(NoteEvent::Panic);
+    }
+
+    /// Sends a hard reset: the audio thread drops every active voice and
+    /// rebuilds its effects chain's DSP state, but leaves every parameter
+    /// (volume, envelope, waveform, effect settings) untouched. For
+    /// recovering from stuck notes or a runaway effect, distinct from
+    /// `panic_all_notes_off`'s plain note-off.
+    pub fn panic_reset(&mut self) {
+        let _ = self.note_producer.push(NoteEvent::PanicReset);
+    }
+
+    /// Sends a sustain pedal (MIDI CC64) state change. Held notes released
+    /// while the pedal is down keep sounding until it's lifted.
+    pub fn set_sustain_pedal(&mut self, down: bool) {
+        let _ = self.note_producer.push(NoteEvent::SustainPedal(down));
+    }
+
+    /// Runs `core::synth::benchmark::run` on a scratch `Synth` (entirely
+    /// separate from the live audio thread) and stores the formatted report
+    /// in `benchmark_report`. Takes several seconds per configuration and
+    /// blocks the calling thread for the duration — this app has no
+    /// existing pattern for offloading UI-triggered work onto a background
+    /// thread, so the "Run Benchmark" button freezes the UI until it
+    /// returns, same as any other synchronous `SynthApp` method.
+    pub fn run_benchmark(&mut self) {
+        self.benchmark_report = Some(crate::core::synth::benchmark::format_table(&crate::core::synth::benchmark::run()));
+    }
+
+    /// Freezes the oscilloscope on its current waveform, for `oscilloscope`'s
+    /// "Hold" button. Replaces any previously held trace.
+    pub fn freeze_oscilloscope(&mut self) {
+        self.oscilloscope_hold = Some(self.analyzer.current_waveform_samples());
+    }
+
+    /// Releases a held oscilloscope trace, if any, so it goes back to
+    /// showing only the live waveform.
+    pub fn release_oscilloscope_hold(&mut self) {
+        self.oscilloscope_hold = None;
+    }
+}
+
+/// Where the auto-save recovery session is written, alongside other app
+/// config rather than next to the user's own project files.
+fn recovery_file_path() -> std::path::PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("audio_theorem")
+        .join("recovery.json")
+}
+
+/// Everything `create_stream` needs to open the output device and wire up
+/// the audio callback. Grouped into one struct rather than passed as
+/// separate parameters since both call sites (`with_settings`,
+/// `change_audio_devices`) build the same set of channel halves and shared
+/// atomics together anyway.
+struct CreateStreamConfig {
+    parameter_consumer: triple_buffer::Output<SynthParameters>,
+    note_consumer: rtrb::Consumer<NoteEvent>,
+    buffer_size_frames: Option<u32>,
+    cpu_load_bits: Arc<AtomicU32>,
+    message_sender: std::sync::mpsc::Sender<SynthMessage>,
+    waveform_producer: triple_buffer::Input<Vec<f32>>,
+    waveform_capture_enabled: Arc<AtomicBool>,
+    osc_peak_levels: [Arc<AtomicU32>; 3],
+    osc_clipped: [Arc<AtomicBool>; 3],
+    master_peak_level: Arc<AtomicU32>,
+    master_clipped: Arc<AtomicBool>,
+    input_consumer: rtrb::Consumer<f32>,
+    input_mix_bits: Arc<AtomicU32>,
+}
+
+/// Opens the default output device and starts the audio callback.
+///
+/// The audio thread owns its `Synth` directly; it never takes a lock.
+/// Parameter updates arrive via the `triple_buffer` consumer (always reads
+/// the latest published snapshot, never blocks), and note events arrive via
+/// the lock-free SPSC `rtrb` queue.
+fn create_stream(config: CreateStreamConfig) -> Result<(Stream, bool, f32), cpal::BuildStreamError> {
+    let CreateStreamConfig {
+        mut parameter_consumer,
+        mut note_consumer,
+        buffer_size_frames,
+        cpu_load_bits,
+        message_sender,
+        mut waveform_producer,
+        waveform_capture_enabled,
+        osc_peak_levels,
+        osc_clipped,
+        master_peak_level,
+        master_clipped,
+        mut input_consumer,
+        input_mix_bits,
+    } = config;
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .expect("no output device available");
+    let default_config = device
+        .default_output_config()
+        .expect("no default output config");
+    let supported_range = *default_config.buffer_size();
+    let mut config = default_config.config();
+
+    let mut fell_back = false;
+    if let Some(frames) = buffer_size_frames {
+        let in_range = match supported_range {
+            cpal::SupportedBufferSize::Range { min, max } => frames >= min && frames <= max,
+            cpal::SupportedBufferSize::Unknown => true,
+        };
+        if in_range {
+            config.buffer_size = cpal::BufferSize::Fixed(frames);
+        } else {
+            fell_back = true;
+        }
+    }
+
+    let sample_rate = config.sample_rate.0 as f32;
+    let channels = config.channels as usize;
+    let mut synth = Synth::new(sample_rate);
+    synth.osc_peak_levels = osc_peak_levels;
+    synth.osc_clipped = osc_clipped;
+    synth.master_peak_level = master_peak_level;
+    synth.master_clipped = master_clipped;
+    let mut load_history = [0.0f32; CPU_LOAD_WINDOW];
+    let mut load_index = 0usize;
+    let mut overload_streak = 0u32;
+
+    let stream = device.build_output_stream(
+        &config,
+        move |data: &mut [f32], _| {
+            let callback_started_at = Instant::now();
+
+            // Drain pending note events first so they land before this
+            // callback's parameter snapshot is applied.
+            while let Ok(event) = note_consumer.pop() {
+                match event {
+                    NoteEvent::On(note, velocity) => synth.note_on(note, velocity),
+                    NoteEvent::Off(note) => synth.note_off(note),
+                    NoteEvent::Panic => synth.all_notes_off(),
+                    NoteEvent::PanicReset => synth.panic_reset(),
+                    NoteEvent::SustainPedal(down) => synth.set_sustain_pedal(down),
+                }
+            }
+
+            let parameters = parameter_consumer.read();
+            synth.oscillator_templates = parameters.oscillator_templates.clone();
+            synth.set_a4_tuning_hz(parameters.a4_tuning_hz);
+            synth.retrigger_mode = parameters.retrigger_mode;
+            synth.scale_quantize = parameters.scale_quantize.clone();
+            synth.velocity_scaling = parameters.velocity_scaling;
+            synth.auto_limiter_enabled = parameters.auto_limiter_enabled;
+            synth.combination_mode = parameters.combination_mode.clone();
+            synth.macros = parameters.macros.clone();
+            synth.pitch_bend = parameters.pitch_bend;
+            // Macro routes modulate the patch above, so they're re-applied
+            // after it lands rather than folded into `parameters` itself —
+            // otherwise a macro's effect would be overwritten by this very
+            // sync on every single callback.
+            synth.reapply_macros();
+            synth.input_mix = f32::from_bits(input_mix_bits.load(Ordering::Relaxed));
+
+            while let Ok(sample) = input_consumer.pop() {
+                synth.input_buffer.push_back(sample);
+            }
+
+            synth.render_block(data, channels);
+            if waveform_capture_enabled.load(Ordering::Relaxed) {
+                // Write into the triple buffer's own back buffer in place
+                // instead of handing it a fresh `Vec` every callback.
+                let back_buffer = waveform_producer.input_buffer_mut();
+                back_buffer.clear();
+                back_buffer.extend_from_slice(data);
+                waveform_producer.publish();
+            }
+
+            let elapsed = callback_started_at.elapsed().as_secs_f32();
+            let budget = data.len() as f32 / sample_rate;
+            load_history[load_index] = elapsed / budget;
+            load_index = (load_index + 1) % CPU_LOAD_WINDOW;
+            let average = load_history.iter().sum::<f32>() / CPU_LOAD_WINDOW as f32;
+            cpu_load_bits.store(average.to_bits(), Ordering::Relaxed);
+
+            if average > 0.95 {
+                overload_streak += 1;
+                if overload_streak > OVERLOAD_STREAK_THRESHOLD {
+                    let _ = message_sender.send(SynthMessage::AudioOverload);
+                    overload_streak = 0;
+                }
+            } else {
+                overload_streak = 0;
+            }
+        },
+        |err| eprintln!("audio stream error: {err}"),
+        None,
+    )?;
+
+    stream.play().expect("failed to start audio stream");
+    Ok((stream, fell_back, sample_rate))
+}
+
+/// How long the input VU meter's peak reading takes to decay back toward
+/// silence after the loudest sample in a block. Mirrors `PEAK_HOLD_SECS` in
+/// `core/synth/audio.rs`, duplicated here since this callback runs on its
+/// own device/stream independent of the output callback's `Synth`.
+const INPUT_PEAK_HOLD_SECS: f32 = 0.3;
+
+/// Opens the default input device and starts capturing into `input_producer`
+/// for the output callback to drain into `Synth::input_buffer`.
+fn create_input_stream(
+    mut input_producer: rtrb::Producer<f32>,
+    input_peak_level: Arc<AtomicU32>,
+) -> Result<Stream, cpal::BuildStreamError> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .expect("no input device available");
+    let config = device
+        .default_input_config()
+        .expect("no default input config")
+        .config();
+    let sample_rate = config.sample_rate.0 as f32;
+
+    let stream = device.build_input_stream(
+        &config,
+        move |data: &[f32], _| {
+            let decay_factor = (1.0 - 1.0 / (sample_rate * INPUT_PEAK_HOLD_SECS)).powi(data.len() as i32);
+            let block_peak = data.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+            let current = f32::from_bits(input_peak_level.load(Ordering::Relaxed));
+            let updated = block_peak.max(current * decay_factor);
+            input_peak_level.store(updated.to_bits(), Ordering::Relaxed);
+
+            for &sample in data {
+                let _ = input_producer.push(sample);
+            }
+        },
+        |err| eprintln!("input stream error: {err}"),
+        None,
+    )?;
+
+    stream.play().expect("failed to start input stream");
+    Ok(stream)
+}
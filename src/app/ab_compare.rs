@@ -0,0 +1,42 @@
+use crate::core::synth::preset::SynthPreset;
+
+/// Holds two patch slots so the user can quickly audition A against B.
+#[derive(Debug, Clone, Default)]
+pub struct AbCompare {
+    pub slot_a: Option<SynthPreset>,
+    pub slot_b: Option<SynthPreset>,
+    pub active: ActiveSlot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ActiveSlot {
+    #[default]
+    A,
+    B,
+}
+
+impl AbCompare {
+    pub fn store_current(&mut self, preset: SynthPreset) {
+        match self.active {
+            ActiveSlot::A => self.slot_a = Some(preset),
+            ActiveSlot::B => self.slot_b = Some(preset),
+        }
+    }
+
+    /// Switches the active slot and returns the preset that should now be
+    /// loaded into the synth, if that slot has one.
+    pub fn toggle(&mut self) -> Option<&SynthPreset> {
+        self.active = match self.active {
+            ActiveSlot::A => ActiveSlot::B,
+            ActiveSlot::B => ActiveSlot::A,
+        };
+        self.current()
+    }
+
+    pub fn current(&self) -> Option<&SynthPreset> {
+        match self.active {
+            ActiveSlot::A => self.slot_a.as_ref(),
+            ActiveSlot::B => self.slot_b.as_ref(),
+        }
+    }
+}
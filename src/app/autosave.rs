@@ -0,0 +1,46 @@
+use std::time::{Duration, Instant};
+
+use crate::core::session::Session;
+
+/// Periodically writes the current session to a recovery file, and on
+/// startup can hand back a crashed session for the user to restore.
+pub struct AutoSave {
+    interval: Duration,
+    last_saved_at: Instant,
+    recovery_path: std::path::PathBuf,
+}
+
+impl AutoSave {
+    pub fn new(interval_minutes: u32, recovery_path: std::path::PathBuf) -> Self {
+        Self {
+            interval: Duration::from_secs(interval_minutes as u64 * 60),
+            last_saved_at: Instant::now(),
+            recovery_path,
+        }
+    }
+
+    /// Call once per UI frame; writes `session` to the recovery path if
+    /// the interval has elapsed.
+    pub fn maybe_save(&mut self, session: &Session) -> std::io::Result<()> {
+        if self.last_saved_at.elapsed() < self.interval {
+            return Ok(());
+        }
+        session.save_to_file(&self.recovery_path)?;
+        self.last_saved_at = Instant::now();
+        Ok(())
+    }
+
+    /// Loads a leftover recovery file from a previous crash, if present.
+    /// The caller is responsible for deleting it once the user has chosen
+    /// to discard or has successfully restored it.
+    pub fn recover(&self) -> Option<Session> {
+        Session::load_from_file(&self.recovery_path).ok()
+    }
+
+    pub fn clear_recovery_file(&self) -> std::io::Result<()> {
+        if self.recovery_path.exists() {
+            std::fs::remove_file(&self.recovery_path)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use super::SynthApp;
+use crate::core::synth::preset::SynthPreset;
+
+impl SynthApp {
+    /// Writes `preset`'s JSON plus any WAV files referenced by `sample_paths`
+    /// into a single zip archive at `output_path`, for sharing a patch
+    /// together with its custom wavetables.
+    pub fn export_preset_bundle(
+        &self,
+        preset: &SynthPreset,
+        sample_paths: &[&Path],
+        output_path: &Path,
+    ) -> std::io::Result<()> {
+        let file = File::create(output_path)?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        let preset_json = preset
+            .to_json()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        zip.start_file("preset.json", options)?;
+        zip.write_all(preset_json.as_bytes())?;
+
+        for sample_path in sample_paths {
+            let file_name = sample_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "sample path has no file name")
+                })?;
+            let contents = std::fs::read(sample_path)?;
+            zip.start_file(format!("samples/{file_name}"), options)?;
+            zip.write_all(&contents)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+}
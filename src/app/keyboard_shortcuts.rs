@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An action the synth UI can perform in response to a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    PanicAllNotesOff,
+    TogglePlayback,
+    SavePreset,
+    LoadPreset,
+    FocusOscillator1,
+    FocusOscillator2,
+    TapTempo,
+}
+
+/// User-configurable keyboard shortcut bindings, keyed by `egui::Key` name
+/// (e.g. `"P"`, `"Space"`) so they can be edited and persisted as plain
+/// strings without depending on egui's key enum layout in storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyboardShortcuts {
+    bindings: HashMap<String, ShortcutAction>,
+}
+
+impl Default for KeyboardShortcuts {
+    fn default() -> Self {
+        let mut bindings = HashMap::new();
+        bindings.insert("Escape".to_string(), ShortcutAction::PanicAllNotesOff);
+        bindings.insert("Space".to_string(), ShortcutAction::TogglePlayback);
+        bindings.insert("S".to_string(), ShortcutAction::SavePreset);
+        bindings.insert("L".to_string(), ShortcutAction::LoadPreset);
+        bindings.insert("1".to_string(), ShortcutAction::FocusOscillator1);
+        bindings.insert("2".to_string(), ShortcutAction::FocusOscillator2);
+        bindings.insert("T".to_string(), ShortcutAction::TapTempo);
+        Self { bindings }
+    }
+}
+
+impl KeyboardShortcuts {
+    pub fn bind(&mut self, key_name: &str, action: ShortcutAction) {
+        self.bindings.insert(key_name.to_string(), action);
+    }
+
+    pub fn action_for_key(&self, key_name: &str) -> Option<ShortcutAction> {
+        self.bindings.get(key_name).copied()
+    }
+
+    /// Scans `ctx`'s input for any key whose name has a binding and returns
+    /// the corresponding actions for this frame, so the caller can apply
+    /// them without polling each key individually.
+    pub fn triggered_actions(&self, ctx: &egui::Context) -> Vec<ShortcutAction> {
+        ctx.input(|input| {
+            self.bindings
+                .iter()
+                .filter(|(key_name, _)| {
+                    egui::Key::from_name(key_name)
+                        .map(|key| input.key_pressed(key))
+                        .unwrap_or(false)
+                })
+                .map(|(_, action)| *action)
+                .collect()
+        })
+    }
+}
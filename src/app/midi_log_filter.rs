@@ -0,0 +1,37 @@
+use crate::core::midi::activity_log::MidiMessageType;
+
+/// Which `MidiMessageType`s the MIDI settings panel's activity log should
+/// display. Checkboxes in the panel toggle membership; everything is shown
+/// by default.
+#[derive(Debug, Clone)]
+pub struct MidiLogFilter {
+    pub show_note_on: bool,
+    pub show_note_off: bool,
+    pub show_control_change: bool,
+    pub show_pitch_bend: bool,
+    pub show_polyphonic_aftertouch: bool,
+}
+
+impl Default for MidiLogFilter {
+    fn default() -> Self {
+        Self {
+            show_note_on: true,
+            show_note_off: true,
+            show_control_change: true,
+            show_pitch_bend: true,
+            show_polyphonic_aftertouch: true,
+        }
+    }
+}
+
+impl MidiLogFilter {
+    pub fn allows(&self, message_type: MidiMessageType) -> bool {
+        match message_type {
+            MidiMessageType::NoteOn => self.show_note_on,
+            MidiMessageType::NoteOff => self.show_note_off,
+            MidiMessageType::ControlChange => self.show_control_change,
+            MidiMessageType::PitchBend => self.show_pitch_bend,
+            MidiMessageType::PolyphonicAftertouch => self.show_polyphonic_aftertouch,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Persistent, user-editable application settings, independent of the
+/// per-patch `SynthParameters` that get published to the audio thread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    /// Fixed audio callback buffer size in frames. `None` lets the driver
+    /// pick its own default.
+    pub buffer_size_frames: Option<u32>,
+    pub auto_save_interval_minutes: u32,
+    /// Maps MIDI Program Change numbers (0-127) to preset names, so a
+    /// hardware controller's patch buttons can switch presets live.
+    #[serde(default)]
+    pub program_change_map: HashMap<u8, String>,
+    /// Whether `SynthApp` should open an input stream and mix captured
+    /// audio (e.g. a guitar or mic) into the synth's signal path.
+    #[serde(default)]
+    pub input_capture_enabled: bool,
+    /// Linear gain applied to captured input before it's mixed in.
+    #[serde(default = "default_input_mix")]
+    pub input_mix: f32,
+}
+
+fn default_input_mix() -> f32 {
+    1.0
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            buffer_size_frames: None,
+            auto_save_interval_minutes: 5,
+            program_change_map: HashMap::new(),
+            input_capture_enabled: false,
+            input_mix: default_input_mix(),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+
+use crate::core::synth::frequency_to_midi_note;
+
+/// Minimum waveform-history amplitude for `get_tuner_reading` to consider a
+/// note "actively playing" rather than silence/noise floor.
+const TUNER_SIGNAL_THRESHOLD: f32 = 0.01;
+
+/// A chromatic tuner reading for the analyzer panel's "Tuner" tab:
+/// the nearest MIDI note to the detected pitch, and how far off it is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TunerReading {
+    pub closest_note: u8,
+    /// How far the detected pitch is from `closest_note`, in cents,
+    /// `-50.0..=50.0` (beyond that range a different note is closer).
+    pub cents_deviation: f32,
+}
+
+/// Captures recent audio-thread output for UI-side visualization
+/// (oscilloscope, spectrum, meters). Samples are pushed from the audio
+/// callback and read from the UI thread; `Analyzer` only ever holds a
+/// fixed-size history, so it's cheap to snapshot every frame.
+pub struct Analyzer {
+    waveform_history: VecDeque<f32>,
+    left_history: VecDeque<f32>,
+    right_history: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl Analyzer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            waveform_history: VecDeque::with_capacity(capacity),
+            left_history: VecDeque::with_capacity(capacity),
+            right_history: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Appends interleaved stereo samples, for the Lissajous/XY stereo
+    /// width visualizer.
+    pub fn push_stereo_samples(&mut self, interleaved: &[f32]) {
+        for pair in interleaved.chunks_exact(2) {
+            if self.left_history.len() >= self.capacity {
+                self.left_history.pop_front();
+                self.right_history.pop_front();
+            }
+            self.left_history.push_back(pair[0]);
+            self.right_history.push_back(pair[1]);
+        }
+    }
+
+    /// `(left, right)` sample pairs for drawing the stereo scope.
+    pub fn current_stereo_samples(&self) -> Vec<(f32, f32)> {
+        self.left_history.iter().copied().zip(self.right_history.iter().copied()).collect()
+    }
+
+    /// Appends `samples` to the rolling history, dropping the oldest
+    /// samples once `capacity` is exceeded.
+    pub fn push_samples(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            if self.waveform_history.len() >= self.capacity {
+                self.waveform_history.pop_front();
+            }
+            self.waveform_history.push_back(sample);
+        }
+    }
+
+    /// The most recent samples, oldest first, for drawing an oscilloscope.
+    pub fn current_waveform_samples(&self) -> Vec<f32> {
+        self.waveform_history.iter().copied().collect()
+    }
+
+    /// True if any sample in the current waveform history exceeds `1.0` in
+    /// absolute value, i.e. would clip on output. `Synth::master_clipped`
+    /// (set directly by the audio callback) is the latched version of this
+    /// same check; this is the unlatched, analyzer-side equivalent for
+    /// callers that only have an `Analyzer` snapshot to work with.
+    pub fn detect_clipping(&self) -> bool {
+        self.waveform_history.iter().any(|sample| sample.abs() > 1.0)
+    }
+
+    /// Chromatic tuner reading for the currently detected pitch, or `None`
+    /// if the waveform history is silent (below `TUNER_SIGNAL_THRESHOLD`)
+    /// or too short/unpitched for `detect_dominant_pitch` to find a note.
+    pub fn get_tuner_reading(&self, sample_rate: f32) -> Option<TunerReading> {
+        let peak = self.waveform_history.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        if peak < TUNER_SIGNAL_THRESHOLD {
+            return None;
+        }
+        let frequency_hz = self.detect_dominant_pitch(sample_rate, 40.0, 2_000.0)?;
+        let (closest_note, cents_deviation) = frequency_to_midi_note(frequency_hz);
+        Some(TunerReading { closest_note, cents_deviation })
+    }
+
+    /// Estimates the dominant pitch in the current waveform history via
+    /// autocorrelation, searching periods corresponding to `min_hz..max_hz`.
+    /// Returns `None` if the history is too short or no clear periodicity
+    /// is found.
+    pub fn detect_dominant_pitch(&self, sample_rate: f32, min_hz: f32, max_hz: f32) -> Option<f32> {
+        let samples: Vec<f32> = self.waveform_history.iter().copied().collect();
+        let min_lag = (sample_rate / max_hz) as usize;
+        let max_lag = (sample_rate / min_hz) as usize;
+        if samples.len() <= max_lag + 1 {
+            return None;
+        }
+
+        let mut best_lag = None;
+        let mut best_correlation = 0.0f32;
+        for lag in min_lag.max(1)..=max_lag {
+            let correlation: f32 = samples[..samples.len() - lag]
+                .iter()
+                .zip(samples[lag..].iter())
+                .map(|(a, b)| a * b)
+                .sum();
+            if correlation > best_correlation {
+                best_correlation = correlation;
+                best_lag = Some(lag);
+            }
+        }
+
+        best_lag.map(|lag| sample_rate / lag as f32)
+    }
+
+    /// Computes a magnitude spectrum of the most recent `fft_size` samples
+    /// in the waveform history, as `[frequency_hz, magnitude]` pairs for
+    /// bins `0..fft_size/2` (Nyquist and above are discarded). Applies a
+    /// Hann window to reduce spectral leakage. A plain O(n^2) DFT rather
+    /// than a real FFT, since `fft_size` here is small enough (a few
+    /// hundred samples) that the simpler implementation is fast enough and
+    /// avoids pulling in an FFT crate for one panel. Returns an empty `Vec`
+    /// if the history is shorter than `fft_size`.
+    pub fn compute_fft(&self, sample_rate: f32, fft_size: usize) -> Vec<[f32; 2]> {
+        if self.waveform_history.len() < fft_size || fft_size < 2 {
+            return Vec::new();
+        }
+        let windowed: Vec<f32> = self
+            .waveform_history
+            .iter()
+            .rev()
+            .take(fft_size)
+            .rev()
+            .enumerate()
+            .map(|(i, &sample)| {
+                let window = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (fft_size - 1) as f32).cos();
+                sample * window
+            })
+            .collect();
+
+        (0..fft_size / 2)
+            .map(|bin| {
+                let (mut re, mut im) = (0.0, 0.0);
+                for (n, &sample) in windowed.iter().enumerate() {
+                    let angle = -2.0 * std::f32::consts::PI * bin as f32 * n as f32 / fft_size as f32;
+                    re += sample * angle.cos();
+                    im += sample * angle.sin();
+                }
+                let magnitude = (re * re + im * im).sqrt() / fft_size as f32;
+                let frequency_hz = bin as f32 * sample_rate / fft_size as f32;
+                [frequency_hz, magnitude]
+            })
+            .collect()
+    }
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new(4096)
+    }
+}
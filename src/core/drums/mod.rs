@@ -0,0 +1,156 @@
+use crate::core::synth::samples::CustomWavetable;
+
+/// Number of steps in one drum pattern.
+pub const STEPS_PER_PATTERN: usize = 16;
+/// Number of independent sample tracks.
+pub const TRACK_COUNT: usize = 8;
+/// Number of patterns the machine can hold, switchable at pattern boundaries.
+pub const PATTERN_COUNT: usize = 16;
+
+/// A short pitch-drop envelope applied to a track's playback rate, giving
+/// kicks and toms their characteristic downward "thump" without needing the
+/// full synth voice envelope system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PitchEnvelope {
+    pub start_semitones: f32,
+    pub decay_seconds: f32,
+}
+
+impl Default for PitchEnvelope {
+    fn default() -> Self {
+        Self {
+            start_semitones: 0.0,
+            decay_seconds: 0.05,
+        }
+    }
+}
+
+impl PitchEnvelope {
+    /// Semitone offset still remaining `elapsed_seconds` after the trigger,
+    /// decaying exponentially to zero.
+    pub fn semitone_offset(&self, elapsed_seconds: f32) -> f32 {
+        if self.decay_seconds <= 0.0 {
+            return 0.0;
+        }
+        self.start_semitones * (-elapsed_seconds / self.decay_seconds).exp()
+    }
+}
+
+/// One of the 8 sample lanes. `wavetable_index` points into
+/// `DrumMachine::wavetables`; `None` means the track has no sample assigned.
+#[derive(Debug, Clone, Default)]
+pub struct DrumTrack {
+    pub wavetable_index: Option<usize>,
+    pub volume: f32,
+    pub pan: f32,
+    pub pitch_tune: f32,
+    pub pitch_envelope: PitchEnvelope,
+}
+
+impl DrumTrack {
+    pub fn new() -> Self {
+        Self {
+            wavetable_index: None,
+            volume: 1.0,
+            pan: 0.0,
+            pitch_tune: 0.0,
+            pitch_envelope: PitchEnvelope::default(),
+        }
+    }
+}
+
+/// One 16-step x 8-track arrangement. `steps[track][step]` is true where
+/// that track should trigger.
+#[derive(Debug, Clone)]
+pub struct DrumPattern {
+    pub steps: [[bool; STEPS_PER_PATTERN]; TRACK_COUNT],
+}
+
+impl Default for DrumPattern {
+    fn default() -> Self {
+        Self {
+            steps: [[false; STEPS_PER_PATTERN]; TRACK_COUNT],
+        }
+    }
+}
+
+/// Playback state for a single track's currently-sounding one-shot.
+#[derive(Debug, Clone, Copy, Default)]
+struct TrackVoice {
+    position: f32,
+    elapsed_seconds: f32,
+}
+
+/// A sample-based drum engine that runs alongside `Synth`, sharing the same
+/// sequencer clock. Call `tick` once per step to both trigger new hits and
+/// render the mixed stereo output for that step's sample window.
+pub struct DrumMachine {
+    pub patterns: Vec<DrumPattern>,
+    pub tracks: [DrumTrack; TRACK_COUNT],
+    pub wavetables: Vec<CustomWavetable>,
+    pub current_pattern: usize,
+    pub sample_rate: f32,
+    voices: [Option<TrackVoice>; TRACK_COUNT],
+}
+
+impl DrumMachine {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            patterns: (0..PATTERN_COUNT).map(|_| DrumPattern::default()).collect(),
+            tracks: std::array::from_fn(|_| DrumTrack::new()),
+            wavetables: Vec::new(),
+            current_pattern: 0,
+            sample_rate,
+            voices: [None; TRACK_COUNT],
+        }
+    }
+
+    /// Triggers any tracks armed on `step` of the current pattern, advances
+    /// every already-sounding voice by one sample, and returns the mixed
+    /// `(left, right)` sample.
+    pub fn tick(&mut self, step: usize) -> (f32, f32) {
+        let pattern = &self.patterns[self.current_pattern];
+        for track_index in 0..TRACK_COUNT {
+            if pattern.steps[track_index][step % STEPS_PER_PATTERN] {
+                self.voices[track_index] = Some(TrackVoice::default());
+            }
+        }
+
+        let mut left = 0.0;
+        let mut right = 0.0;
+        for track_index in 0..TRACK_COUNT {
+            let Some(voice) = self.voices[track_index].as_mut() else { continue };
+            let track = &self.tracks[track_index];
+            let Some(wavetable_index) = track.wavetable_index else {
+                self.voices[track_index] = None;
+                continue;
+            };
+            let Some(wavetable) = self.wavetables.get(wavetable_index) else {
+                self.voices[track_index] = None;
+                continue;
+            };
+            let Some(frame) = wavetable.frames.first() else {
+                self.voices[track_index] = None;
+                continue;
+            };
+
+            let pitch_offset = track.pitch_envelope.semitone_offset(voice.elapsed_seconds);
+            let semitones = track.pitch_tune + pitch_offset;
+            let playback_rate = 2.0f32.powf(semitones / 12.0);
+
+            let index = voice.position as usize;
+            let sample = frame.get(index).copied().unwrap_or(0.0) * track.volume;
+            let pan = track.pan.clamp(-1.0, 1.0);
+            left += sample * (1.0 - pan.max(0.0));
+            right += sample * (1.0 + pan.min(0.0));
+
+            voice.elapsed_seconds += 1.0 / self.sample_rate;
+            match wavetable.advance_position(frame, voice.position, playback_rate) {
+                Some(next) => voice.position = next,
+                None => self.voices[track_index] = None,
+            }
+        }
+
+        (left, right)
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// How `Compressor` measures the signal level it compares against
+/// `threshold_db`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DetectionMode {
+    /// Tracks the instantaneous absolute sample value. Reacts fastest, but
+    /// can pump on transient-heavy material.
+    Peak,
+    /// Tracks a running root-mean-square average. Smoother, closer to
+    /// perceived loudness.
+    Rms,
+}
+
+/// A feed-forward dynamic range compressor with a soft-knee gain computer
+/// and a one-pole envelope follower, meant to sit last in the master
+/// `EffectsChain` to tame clipping from loud patches or heavy polyphony.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Compressor {
+    /// Level, in dBFS, above which gain reduction begins.
+    pub threshold_db: f32,
+    /// Input:output ratio above the threshold, e.g. `4.0` means 4:1.
+    pub ratio: f32,
+    pub attack_ms: f32,
+    pub release_ms: f32,
+    /// Flat gain applied after compression to restore perceived loudness.
+    pub makeup_gain_db: f32,
+    /// Width, in dB, of the soft-knee region centered on `threshold_db`.
+    pub knee_db: f32,
+    pub detection_mode: DetectionMode,
+
+    /// The envelope follower's current level: linear amplitude in
+    /// `DetectionMode::Peak`, mean-square amplitude in `DetectionMode::Rms`
+    /// (square-rooted back to linear only when read for `envelope_db`) —
+    /// smoothing has to happen on the squared signal for the result to
+    /// actually be an RMS average rather than just a slower peak follower.
+    #[serde(skip)]
+    envelope: f32,
+    /// Gain reduction applied to the most recently processed sample, in
+    /// dB (always `<= 0.0`), kept around only so the UI can drive a gain
+    /// reduction meter.
+    #[serde(skip)]
+    pub gain_reduction_db: f32,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self {
+            threshold_db: -18.0,
+            ratio: 4.0,
+            attack_ms: 10.0,
+            release_ms: 100.0,
+            makeup_gain_db: 0.0,
+            knee_db: 6.0,
+            detection_mode: DetectionMode::Peak,
+            envelope: 0.0,
+            gain_reduction_db: 0.0,
+        }
+    }
+}
+
+impl Compressor {
+    /// Applies one sample of compression, updating the envelope follower
+    /// and `gain_reduction_db` along the way.
+    pub fn process_sample(&mut self, sample: f32, sample_rate: f32) -> f32 {
+        let detector_input = match self.detection_mode {
+            DetectionMode::Peak => sample.abs(),
+            DetectionMode::Rms => sample * sample,
+        };
+
+        let rising = detector_input > self.envelope;
+        let time_constant_ms = if rising { self.attack_ms } else { self.release_ms };
+        let coefficient = (-1.0 / (0.001 * time_constant_ms.max(0.001) * sample_rate)).exp();
+        self.envelope = detector_input + coefficient * (self.envelope - detector_input);
+
+        let envelope_linear = match self.detection_mode {
+            DetectionMode::Peak => self.envelope,
+            DetectionMode::Rms => self.envelope.max(0.0).sqrt(),
+        };
+        let envelope_db = 20.0 * envelope_linear.max(1e-9).log10();
+        let reduction_db = self.gain_computer_db(envelope_db);
+        self.gain_reduction_db = reduction_db;
+
+        let total_gain_db = reduction_db + self.makeup_gain_db;
+        sample * 10.0f32.powf(total_gain_db / 20.0)
+    }
+
+    /// Soft-knee gain computer: returns the gain reduction (`<= 0.0` dB)
+    /// to apply for an envelope reading of `envelope_db`.
+    fn gain_computer_db(&self, envelope_db: f32) -> f32 {
+        let knee_half = self.knee_db / 2.0;
+        let overshoot = envelope_db - self.threshold_db;
+
+        let compressed_db = if overshoot <= -knee_half {
+            envelope_db
+        } else if overshoot >= knee_half {
+            self.threshold_db + overshoot / self.ratio
+        } else {
+            // Interpolate the slope across the knee so the transition from
+            // 1:1 to 1:ratio is smooth instead of a hard corner.
+            let knee_position = overshoot + knee_half;
+            envelope_db + (1.0 / self.ratio - 1.0) * knee_position * knee_position / (2.0 * self.knee_db.max(1e-6))
+        };
+
+        compressed_db - envelope_db
+    }
+}
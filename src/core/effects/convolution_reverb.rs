@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::path::Path;
+
+use crate::core::synth::samples::{load_sample, ChannelConversion};
+
+/// Impulse responses longer than this take noticeably more CPU per sample
+/// under `process_sample`'s direct time-domain convolution (there's no FFT
+/// crate in this codebase — `Analyzer::compute_fft` avoids one for the same
+/// reason — so this is a warn, not a hard limit).
+pub const LONG_IR_WARNING_SECONDS: f32 = 2.0;
+
+/// A reverb effect that convolves its input against a loaded impulse
+/// response WAV, for real acoustic spaces rather than an algorithmic
+/// approximation. Unlike `EffectsChain`'s other effects, this one needs a
+/// history buffer as long as the IR itself, so `process_sample` is a direct
+/// (not FFT-based) convolution: straightforward, and correct regardless of
+/// IR length, at the cost of `O(impulse_response.len())` per sample.
+#[derive(Debug, Clone)]
+pub struct ConvolutionReverb {
+    pub impulse_response: Vec<f32>,
+    pub sample_rate: u32,
+    /// True if `impulse_response.len()` exceeds `LONG_IR_WARNING_SECONDS`
+    /// worth of samples at `sample_rate`, for the effects panel to surface
+    /// a CPU-cost warning.
+    pub is_long_ir: bool,
+    /// Silence inserted before the IR starts contributing, in samples.
+    pub pre_delay_samples: usize,
+    /// Fraction (`0.0..=1.0`) of `impulse_response` actually used, from the
+    /// start; trims the tail without needing to reload a shorter file.
+    pub trim: f32,
+    /// Ring buffer of the last `impulse_response.len() + pre_delay_samples`
+    /// input samples, most recent last.
+    history: VecDeque<f32>,
+}
+
+impl ConvolutionReverb {
+    /// Loads a WAV impulse response, reusing `load_sample`'s mono-fold and
+    /// sample-rate-mismatch reporting (the same as `CustomWavetable` loads).
+    /// Resampling a mismatched-rate IR isn't implemented — same limitation
+    /// `load_sample` callers already live with for custom wavetables.
+    pub fn load_ir(path: &Path, sample_rate: f32) -> Result<Self, hound::Error> {
+        let (wavetable, report) = load_sample(path, sample_rate as u32, ChannelConversion::MixDown)?;
+        let impulse_response = wavetable.frames.into_iter().next().unwrap_or_default();
+        let is_long_ir = impulse_response.len() as f32 / report.loaded_sample_rate.max(1) as f32 > LONG_IR_WARNING_SECONDS;
+
+        Ok(Self {
+            impulse_response,
+            sample_rate: report.loaded_sample_rate,
+            is_long_ir,
+            pre_delay_samples: 0,
+            trim: 1.0,
+            history: VecDeque::new(),
+        })
+    }
+
+    /// The IR samples actually convolved against, honoring `trim`.
+    fn active_impulse_response(&self) -> &[f32] {
+        let len = ((self.impulse_response.len() as f32) * self.trim.clamp(0.0, 1.0)) as usize;
+        &self.impulse_response[..len.min(self.impulse_response.len())]
+    }
+
+    /// Convolves one input sample against the (trimmed) impulse response,
+    /// delayed by `pre_delay_samples`.
+    pub fn process_sample(&mut self, input: f32) -> f32 {
+        let impulse_response_len = self.active_impulse_response().len();
+        if impulse_response_len == 0 {
+            return input;
+        }
+
+        self.history.push_back(input);
+        let max_history = impulse_response_len + self.pre_delay_samples;
+        while self.history.len() > max_history {
+            self.history.pop_front();
+        }
+
+        let impulse_response = self.active_impulse_response();
+        let mut output = 0.0;
+        for (tap, &coefficient) in impulse_response.iter().enumerate() {
+            let history_index = self.history.len() as isize - 1 - self.pre_delay_samples as isize - tap as isize;
+            if history_index < 0 {
+                break;
+            }
+            output += self.history[history_index as usize] * coefficient;
+        }
+        output
+    }
+
+    /// Convolves a whole block at once, for callers that batch processing
+    /// instead of calling `process_sample` per frame.
+    pub fn process_block(&mut self, input: &[f32], output: &mut [f32]) {
+        for (input_sample, output_sample) in input.iter().zip(output.iter_mut()) {
+            *output_sample = self.process_sample(*input_sample);
+        }
+    }
+}
+
+impl Default for ConvolutionReverb {
+    /// An IR-less reverb passes its input through unchanged, the same as
+    /// `PassthroughEffect`, until `load_ir` populates `impulse_response`.
+    fn default() -> Self {
+        Self {
+            impulse_response: Vec::new(),
+            sample_rate: 44_100,
+            is_long_ir: false,
+            pre_delay_samples: 0,
+            trim: 1.0,
+            history: VecDeque::new(),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+/// Which standard Audio EQ Cookbook biquad shape an `EqBand` computes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EqFilterType {
+    /// Boosts or cuts a bell-shaped region centered on `frequency`, width
+    /// set by `q`.
+    Peaking,
+    /// Boosts or cuts everything below `frequency`.
+    LowShelf,
+    /// Boosts or cuts everything above `frequency`.
+    HighShelf,
+    /// Removes a narrow band around `frequency`; `gain_db` is ignored.
+    Notch,
+}
+
+/// One biquad band of a `ParametricEq`. Coefficients are recomputed from
+/// `frequency`/`gain_db`/`q` fresh each sample (same approach `Phaser` uses
+/// for its allpass coefficient) rather than cached and invalidated, since
+/// the UI can change any of them at any time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EqBand {
+    pub frequency: f32,
+    /// Boost/cut in dB, roughly ±20 dB. Ignored by `EqFilterType::Notch`.
+    pub gain_db: f32,
+    /// Bandwidth/resonance; higher narrows a `Peaking`/`Notch` band.
+    pub q: f32,
+    pub filter_type: EqFilterType,
+
+    /// Direct-form I biquad history: the last two inputs and outputs.
+    #[serde(skip)]
+    x1: f32,
+    #[serde(skip)]
+    x2: f32,
+    #[serde(skip)]
+    y1: f32,
+    #[serde(skip)]
+    y2: f32,
+}
+
+impl EqBand {
+    pub fn new(frequency: f32, gain_db: f32, q: f32, filter_type: EqFilterType) -> Self {
+        Self { frequency, gain_db, q, filter_type, x1: 0.0, x2: 0.0, y1: 0.0, y2: 0.0 }
+    }
+
+    /// Audio EQ Cookbook coefficients (Robert Bristow-Johnson) for this
+    /// band's current `filter_type`, normalized so `a0 == 1.0`, i.e.
+    /// `(b0, b1, b2, a1, a2)`.
+    fn coefficients(&self, sample_rate: f32) -> (f32, f32, f32, f32, f32) {
+        let omega = std::f32::consts::TAU * self.frequency.max(1.0) / sample_rate;
+        let cos_omega = omega.cos();
+        let sin_omega = omega.sin();
+        let alpha = sin_omega / (2.0 * self.q.max(0.01));
+        let a = 10.0f32.powf(self.gain_db / 40.0);
+
+        let (b0, b1, b2, a0, a1, a2) = match self.filter_type {
+            EqFilterType::Peaking => (
+                1.0 + alpha * a,
+                -2.0 * cos_omega,
+                1.0 - alpha * a,
+                1.0 + alpha / a,
+                -2.0 * cos_omega,
+                1.0 - alpha / a,
+            ),
+            EqFilterType::LowShelf => {
+                let sqrt_a = a.sqrt();
+                let two_sqrt_a_alpha = 2.0 * sqrt_a * alpha;
+                (
+                    a * ((a + 1.0) - (a - 1.0) * cos_omega + two_sqrt_a_alpha),
+                    2.0 * a * ((a - 1.0) - (a + 1.0) * cos_omega),
+                    a * ((a + 1.0) - (a - 1.0) * cos_omega - two_sqrt_a_alpha),
+                    (a + 1.0) + (a - 1.0) * cos_omega + two_sqrt_a_alpha,
+                    -2.0 * ((a - 1.0) + (a + 1.0) * cos_omega),
+                    (a + 1.0) + (a - 1.0) * cos_omega - two_sqrt_a_alpha,
+                )
+            }
+            EqFilterType::HighShelf => {
+                let sqrt_a = a.sqrt();
+                let two_sqrt_a_alpha = 2.0 * sqrt_a * alpha;
+                (
+                    a * ((a + 1.0) + (a - 1.0) * cos_omega + two_sqrt_a_alpha),
+                    -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_omega),
+                    a * ((a + 1.0) + (a - 1.0) * cos_omega - two_sqrt_a_alpha),
+                    (a + 1.0) - (a - 1.0) * cos_omega + two_sqrt_a_alpha,
+                    2.0 * ((a - 1.0) - (a + 1.0) * cos_omega),
+                    (a + 1.0) - (a - 1.0) * cos_omega - two_sqrt_a_alpha,
+                )
+            }
+            EqFilterType::Notch => (1.0, -2.0 * cos_omega, 1.0, 1.0 + alpha, -2.0 * cos_omega, 1.0 - alpha),
+        };
+
+        (b0 / a0, b1 / a0, b2 / a0, a1 / a0, a2 / a0)
+    }
+
+    /// Applies this band's biquad to one sample, updating its history.
+    pub fn process_sample(&mut self, input: f32, sample_rate: f32) -> f32 {
+        let (b0, b1, b2, a1, a2) = self.coefficients(sample_rate);
+        let output = b0 * input + b1 * self.x1 + b2 * self.x2 - a1 * self.y1 - a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = input;
+        self.y2 = self.y1;
+        self.y1 = output;
+
+        output
+    }
+
+    /// This band's magnitude response in dB at `frequency_hz`, evaluating
+    /// `coefficients`' transfer function at `z = e^{j*omega}`. Used only for
+    /// the effects panel's response-curve overlay, not for audio processing
+    /// (mirroring `Filter::magnitude_response_db`'s UI-only role).
+    pub fn magnitude_response_db(&self, frequency_hz: f32, sample_rate: f32) -> f32 {
+        let (b0, b1, b2, a1, a2) = self.coefficients(sample_rate);
+        let omega = std::f32::consts::TAU * frequency_hz / sample_rate;
+        let (cos1, sin1) = (omega.cos(), omega.sin());
+        let (cos2, sin2) = ((2.0 * omega).cos(), (2.0 * omega).sin());
+
+        let num_re = b0 + b1 * cos1 + b2 * cos2;
+        let num_im = -(b1 * sin1 + b2 * sin2);
+        let den_re = 1.0 + a1 * cos1 + a2 * cos2;
+        let den_im = -(a1 * sin1 + a2 * sin2);
+
+        let num_mag = (num_re * num_re + num_im * num_im).sqrt();
+        let den_mag = (den_re * den_re + den_im * den_im).sqrt().max(1e-9);
+        20.0 * (num_mag / den_mag).max(1e-9).log10()
+    }
+}
+
+impl Default for EqBand {
+    fn default() -> Self {
+        Self::new(1_000.0, 0.0, 0.707, EqFilterType::Peaking)
+    }
+}
+
+/// A 3-band parametric equalizer for the master effects chain: each band is
+/// an independently configurable `EqBand`, applied in series low-to-high.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParametricEq {
+    pub bands: [EqBand; 3],
+}
+
+impl Default for ParametricEq {
+    fn default() -> Self {
+        Self {
+            bands: [
+                EqBand::new(100.0, 0.0, 0.707, EqFilterType::LowShelf),
+                EqBand::new(1_000.0, 0.0, 0.707, EqFilterType::Peaking),
+                EqBand::new(8_000.0, 0.0, 0.707, EqFilterType::HighShelf),
+            ],
+        }
+    }
+}
+
+impl ParametricEq {
+    /// Applies all three bands in series, low-to-high.
+    pub fn process_sample(&mut self, input: f32, sample_rate: f32) -> f32 {
+        self.bands.iter_mut().fold(input, |sample, band| band.process_sample(sample, sample_rate))
+    }
+
+    /// Samples the combined response of all three bands at `count` points
+    /// log-spaced between `min_hz` and `max_hz`, returning `(frequency_hz,
+    /// magnitude_db)` pairs for the effects panel's overlay plot.
+    pub fn response_curve(&self, min_hz: f32, max_hz: f32, count: usize, sample_rate: f32) -> Vec<(f32, f32)> {
+        let log_min = min_hz.max(1.0).ln();
+        let log_max = max_hz.ln();
+        (0..count)
+            .map(|i| {
+                let t = i as f32 / (count - 1).max(1) as f32;
+                let frequency_hz = (log_min + (log_max - log_min) * t).exp();
+                let db = self.bands.iter().map(|band| band.magnitude_response_db(frequency_hz, sample_rate)).sum();
+                (frequency_hz, db)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+
+pub mod compressor;
+pub mod convolution_reverb;
+pub mod eq;
+pub mod phaser;
+
+pub use compressor::{Compressor, DetectionMode};
+pub use convolution_reverb::ConvolutionReverb;
+pub use eq::{EqBand, EqFilterType, ParametricEq};
+pub use phaser::Phaser;
+
+/// Which kind of effect an `EffectSlot` holds, used for the "+" add menu and
+/// for serializing a chain without needing `dyn Effect` to be `Serialize`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EffectType {
+    Chorus,
+    Delay,
+    Reverb,
+    Distortion,
+    Compressor,
+    Phaser,
+    ConvolutionReverb,
+    ParametricEq,
+}
+
+impl EffectType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EffectType::Chorus => "Chorus",
+            EffectType::Delay => "Delay",
+            EffectType::Reverb => "Reverb",
+            EffectType::Distortion => "Distortion",
+            EffectType::Compressor => "Compressor",
+            EffectType::Phaser => "Phaser",
+            EffectType::ConvolutionReverb => "Convolution Reverb",
+            EffectType::ParametricEq => "Parametric EQ",
+        }
+    }
+
+    /// Builds a fresh effect instance with default settings. `Compressor`,
+    /// `Phaser`, `ConvolutionReverb` and `ParametricEq` build their real
+    /// effect; `Chorus`, `Delay`, `Reverb` and `Distortion` have no DSP of
+    /// their own and build a transparent passthrough.
+    /// `ConvolutionReverb` is also a passthrough until an IR is loaded via
+    /// `ConvolutionReverb::load_ir` from the effects panel.
+    pub fn create(&self) -> Box<dyn Effect> {
+        match self {
+            EffectType::Compressor => Box::new(Compressor::default()),
+            EffectType::Phaser => Box::new(Phaser::default()),
+            EffectType::ConvolutionReverb => Box::new(ConvolutionReverb::default()),
+            EffectType::ParametricEq => Box::new(ParametricEq::default()),
+            _ => Box::new(PassthroughEffect { effect_type: *self }),
+        }
+    }
+}
+
+/// One effect in a `Synth`'s signal chain. Implementors own whatever DSP
+/// state they need (delay lines, filter history, etc.) and process audio
+/// one sample at a time to fit the per-sample `Synth::get_sample` path.
+pub trait Effect: std::fmt::Debug + Send {
+    fn effect_type(&self) -> EffectType;
+    fn process(&mut self, input: f32, sample_rate: f32) -> f32;
+
+    /// Downcast hook so effect-specific UI (e.g. the compressor's gain
+    /// reduction meter) can reach past the trait object to its concrete
+    /// fields. Every implementor just returns `self`.
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+}
+
+#[derive(Debug)]
+struct PassthroughEffect {
+    effect_type: EffectType,
+}
+
+impl Effect for PassthroughEffect {
+    fn effect_type(&self) -> EffectType {
+        self.effect_type
+    }
+
+    fn process(&mut self, input: f32, _sample_rate: f32) -> f32 {
+        input
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Effect for Compressor {
+    fn effect_type(&self) -> EffectType {
+        EffectType::Compressor
+    }
+
+    fn process(&mut self, input: f32, sample_rate: f32) -> f32 {
+        self.process_sample(input, sample_rate)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Effect for Phaser {
+    fn effect_type(&self) -> EffectType {
+        EffectType::Phaser
+    }
+
+    fn process(&mut self, input: f32, sample_rate: f32) -> f32 {
+        self.process_sample(input, sample_rate)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Effect for ConvolutionReverb {
+    fn effect_type(&self) -> EffectType {
+        EffectType::ConvolutionReverb
+    }
+
+    fn process(&mut self, input: f32, _sample_rate: f32) -> f32 {
+        self.process_sample(input)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Effect for ParametricEq {
+    fn effect_type(&self) -> EffectType {
+        EffectType::ParametricEq
+    }
+
+    fn process(&mut self, input: f32, sample_rate: f32) -> f32 {
+        self.process_sample(input, sample_rate)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// One position in the chain: the effect itself, plus the bypass toggle and
+/// wet/dry mix that wrap every effect uniformly regardless of its DSP.
+#[derive(Debug)]
+pub struct EffectSlot {
+    pub effect: Box<dyn Effect>,
+    pub enabled: bool,
+    /// 0.0 is fully dry (bypassed in all but name), 1.0 is fully wet.
+    pub wet_dry: f32,
+}
+
+impl EffectSlot {
+    pub fn new(effect_type: EffectType) -> Self {
+        Self {
+            effect: effect_type.create(),
+            enabled: true,
+            wet_dry: 1.0,
+        }
+    }
+
+    pub fn process(&mut self, input: f32, sample_rate: f32) -> f32 {
+        if !self.enabled {
+            return input;
+        }
+        let wet = self.effect.process(input, sample_rate);
+        input * (1.0 - self.wet_dry) + wet * self.wet_dry
+    }
+}
+
+/// An ordered effects chain, processed front-to-back. Order matters (e.g.
+/// distortion before reverb sounds very different from reverb before
+/// distortion), so the UI lets users drag slots to reorder this `Vec`.
+#[derive(Debug, Default)]
+pub struct EffectsChain {
+    pub slots: Vec<EffectSlot>,
+    /// When true, `process` returns its input unchanged without calling
+    /// any slot's `process`. Unlike removing or disabling individual
+    /// slots, this leaves every effect's internal state (delay buffers,
+    /// reverb tails, compressor envelopes) untouched, so toggling bypass
+    /// back off resumes exactly where the wet signal left off instead of
+    /// restarting from silence.
+    pub bypassed: bool,
+}
+
+impl EffectsChain {
+    pub fn push(&mut self, effect_type: EffectType) {
+        self.slots.push(EffectSlot::new(effect_type));
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.slots.len() {
+            self.slots.remove(index);
+        }
+    }
+
+    /// Moves the slot at `from` to `to`, shifting the slots between them.
+    pub fn reorder(&mut self, from: usize, to: usize) {
+        if from >= self.slots.len() || to >= self.slots.len() {
+            return;
+        }
+        let slot = self.slots.remove(from);
+        self.slots.insert(to, slot);
+    }
+
+    /// Runs `input` through every enabled slot in order, or passes it
+    /// through unchanged if `bypassed` is set.
+    pub fn process(&mut self, input: f32, sample_rate: f32) -> f32 {
+        if self.bypassed {
+            return input;
+        }
+        let mut sample = input;
+        for slot in self.slots.iter_mut() {
+            sample = slot.process(sample, sample_rate);
+        }
+        sample
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+/// One allpass filter stage's delay memory (a first-order allpass needs
+/// only its previous input and output).
+#[derive(Debug, Clone, Copy, Default)]
+struct AllPassState {
+    previous_input: f32,
+    previous_output: f32,
+}
+
+impl AllPassState {
+    /// Processes one sample through a first-order allpass with coefficient
+    /// `a`, derived from the stage's current center frequency.
+    fn process(&mut self, input: f32, a: f32) -> f32 {
+        let output = -a * input + self.previous_input + a * self.previous_output;
+        self.previous_input = input;
+        self.previous_output = output;
+        output
+    }
+}
+
+/// A phaser built from a chain of allpass filter stages whose center
+/// frequencies are swept together by one LFO, producing the characteristic
+/// sweeping notches when mixed back with the dry signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Phaser {
+    /// Number of allpass stages in the chain (4-16); more stages produce
+    /// more notches in the swept spectrum.
+    pub stages: usize,
+    /// LFO sweep rate in Hz.
+    pub rate: f32,
+    /// How far the LFO sweeps the stages' center frequency, 0.0-1.0.
+    pub depth: f32,
+    /// Linear gain fed from the chain's output back into its input.
+    pub feedback: f32,
+    /// 0.0 is fully dry, 1.0 is fully wet.
+    pub mix: f32,
+    /// Center of the LFO's frequency sweep, in Hz.
+    pub center_hz: f32,
+
+    #[serde(skip)]
+    lfo_phase: f32,
+    #[serde(skip)]
+    stage_states: Vec<AllPassState>,
+    #[serde(skip)]
+    feedback_memory: f32,
+}
+
+impl Default for Phaser {
+    fn default() -> Self {
+        Self {
+            stages: 4,
+            rate: 0.5,
+            depth: 0.7,
+            feedback: 0.3,
+            mix: 0.5,
+            center_hz: 800.0,
+            lfo_phase: 0.0,
+            stage_states: vec![AllPassState::default(); 4],
+            feedback_memory: 0.0,
+        }
+    }
+}
+
+impl Phaser {
+    /// Sweeps all stages by the LFO's current position and mixes the
+    /// resulting wet signal with `input` per `mix`.
+    pub fn process_sample(&mut self, input: f32, sample_rate: f32) -> f32 {
+        self.stages = self.stages.clamp(4, 16);
+        if self.stage_states.len() != self.stages {
+            self.stage_states.resize(self.stages, AllPassState::default());
+        }
+
+        let lfo = (self.lfo_phase * std::f32::consts::TAU).sin();
+        self.lfo_phase += self.rate / sample_rate;
+        self.lfo_phase -= self.lfo_phase.floor();
+
+        let swept_hz = (self.center_hz * (1.0 + lfo * self.depth)).clamp(20.0, 16_000.0);
+        let tan_term = (std::f32::consts::PI * swept_hz / sample_rate).tan();
+        let coefficient = (tan_term - 1.0) / (tan_term + 1.0);
+
+        let mut sample = input + self.feedback_memory * self.feedback;
+        for stage in self.stage_states.iter_mut() {
+            sample = stage.process(sample, coefficient);
+        }
+        self.feedback_memory = sample;
+
+        input * (1.0 - self.mix) + sample * self.mix
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+/// A synth parameter a `MacroRoute` can drive. Deliberately the same
+/// addressable per-oscillator/per-slot parameters `MidiControlTarget` models
+/// minus `MasterVolume`/`WavetablePosition`, since those have no real field
+/// on `Synth` to write a modulated value into (see `MidiControlTarget`'s own
+/// doc comment on only modeling controls for state that actually exists),
+/// and minus `Macro` itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ModTarget {
+    OscillatorVolume(usize),
+    OscillatorPan(usize),
+    OscillatorDetune(usize),
+    FilterCutoff(usize),
+    FilterResonance(usize),
+    /// Wet/dry mix for the effects-chain slot at this index.
+    EffectWetDry(usize),
+}
+
+impl ModTarget {
+    pub fn label(&self) -> String {
+        match self {
+            ModTarget::OscillatorVolume(i) => format!("Osc {} Volume", i + 1),
+            ModTarget::OscillatorPan(i) => format!("Osc {} Pan", i + 1),
+            ModTarget::OscillatorDetune(i) => format!("Osc {} Detune", i + 1),
+            ModTarget::FilterCutoff(i) => format!("Osc {} Filter Cutoff", i + 1),
+            ModTarget::FilterResonance(i) => format!("Osc {} Filter Resonance", i + 1),
+            ModTarget::EffectWetDry(i) => format!("Effect Slot {} Wet/Dry", i + 1),
+        }
+    }
+
+    /// This target's full useful range, used as the starting `range` for a
+    /// freshly assigned `MacroRoute` (e.g. from the "Assign to Macro N"
+    /// context menu) before the user narrows it.
+    pub fn default_range(&self) -> (f32, f32) {
+        match self {
+            ModTarget::OscillatorVolume(_) => (0.0, 1.0),
+            ModTarget::OscillatorPan(_) => (-1.0, 1.0),
+            ModTarget::OscillatorDetune(_) => (-24.0, 24.0),
+            ModTarget::FilterCutoff(_) => (20.0, 20_000.0),
+            ModTarget::FilterResonance(_) => (0.0, 1.0),
+            ModTarget::EffectWetDry(_) => (0.0, 1.0),
+        }
+    }
+}
+
+/// One parameter a macro drives: `range` maps the macro's `0.0..1.0` value
+/// onto this target's useful range, the same `(min, max)` shape
+/// `MidiMappingEntry`'s output range uses.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MacroRoute {
+    pub target: ModTarget,
+    pub range: (f32, f32),
+}
+
+impl MacroRoute {
+    pub fn new(target: ModTarget, range: (f32, f32)) -> Self {
+        Self { target, range }
+    }
+
+    /// Linearly maps `macro_value` (clamped to `0.0..1.0`) onto `range`.
+    pub fn mapped_value(&self, macro_value: f32) -> f32 {
+        let t = macro_value.clamp(0.0, 1.0);
+        self.range.0 + t * (self.range.1 - self.range.0)
+    }
+}
+
+/// One of a `Synth`'s eight performance macro knobs: a single `0.0..1.0`
+/// slider that fans out to every parameter in `routes`, each scaled to its
+/// own useful range. Live performers map one knob to several parameters at
+/// once (e.g. "brightness" driving both filter cutoff and detune) instead of
+/// reaching for each slider individually mid-performance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroKnob {
+    pub name: String,
+    pub value: f32,
+    pub routes: Vec<MacroRoute>,
+}
+
+impl MacroKnob {
+    pub fn named(name: impl Into<String>) -> Self {
+        Self { name: name.into(), value: 0.0, routes: Vec::new() }
+    }
+}
+
+impl Default for MacroKnob {
+    fn default() -> Self {
+        Self::named("Macro")
+    }
+}
+
+/// Eight unrouted macro knobs named "Macro 1" through "Macro 8", the
+/// starting point for both `Synth::new` and a freshly created preset.
+pub fn default_macros() -> [MacroKnob; 8] {
+    std::array::from_fn(|index| MacroKnob::named(format!("Macro {}", index + 1)))
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// How many entries `MidiActivityLog` keeps before dropping the oldest.
+const LOG_CAPACITY: usize = 100;
+
+/// One received MIDI message, as shown in the activity log panel.
+#[derive(Debug, Clone, Copy)]
+pub struct MidiLogEntry {
+    pub timestamp: Instant,
+    pub message_type: MidiMessageType,
+    pub channel: u8,
+    pub data: [u8; 3],
+}
+
+/// The kinds of message the activity log can filter on independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MidiMessageType {
+    NoteOn,
+    NoteOff,
+    ControlChange,
+    PitchBend,
+    PolyphonicAftertouch,
+}
+
+impl MidiMessageType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MidiMessageType::NoteOn => "Note On",
+            MidiMessageType::NoteOff => "Note Off",
+            MidiMessageType::ControlChange => "CC",
+            MidiMessageType::PitchBend => "Pitch Bend",
+            MidiMessageType::PolyphonicAftertouch => "Poly AT",
+        }
+    }
+}
+
+/// A fixed-capacity ring buffer of recently received MIDI messages, for
+/// the MIDI settings panel's activity log. Safe to share across the MIDI
+/// input callback thread and the UI thread behind an `Arc<Mutex<_>>`.
+#[derive(Debug, Default)]
+pub struct MidiActivityLog {
+    entries: VecDeque<MidiLogEntry>,
+    paused: bool,
+}
+
+impl MidiActivityLog {
+    /// Appends an entry, dropping the oldest once at capacity. No-op while
+    /// paused, so users can freeze the log to read it.
+    pub fn push(&mut self, message_type: MidiMessageType, channel: u8, data: [u8; 3]) {
+        if self.paused {
+            return;
+        }
+        if self.entries.len() >= LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(MidiLogEntry {
+            timestamp: Instant::now(),
+            message_type,
+            channel,
+            data,
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &MidiLogEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+}
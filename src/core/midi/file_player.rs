@@ -0,0 +1,216 @@
+use crate::core::sequencer::{AutomationParameter, AutomationTrack};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// A parsed Standard MIDI File event, already flattened into a single
+/// absolute-tick timeline merged across all tracks, plus recorded
+/// `AutomationTrack` points fired alongside them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackEvent {
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8 },
+    ControlChange { channel: u8, controller: u8, value: u8 },
+    Automation { parameter: AutomationParameter, value: f32 },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TimedEvent {
+    tick: u64,
+    event: PlaybackEvent,
+}
+
+/// Loads and plays back Type 0/1 Standard MIDI Files, emitting channel voice
+/// events at the correct time via `tick`. Tempo changes (`Set Tempo`
+/// meta-events) are honored through a tempo map rather than assuming a
+/// fixed BPM for the whole file.
+pub struct MidiFilePlayer {
+    events: Vec<TimedEvent>,
+    /// Ticks per quarter note, from the file header.
+    ticks_per_quarter: u16,
+    /// `(tick, microseconds_per_quarter)` pairs in ascending tick order.
+    tempo_map: Vec<(u64, u32)>,
+    pub playing: bool,
+    pub loop_playback: bool,
+    pub current_tick: u64,
+    muted_channels: HashSet<u8>,
+    /// Fractional ticks carried over between calls to `tick`.
+    tick_accumulator: f64,
+    last_event_index: usize,
+    automation_tracks: Vec<AutomationTrack>,
+    /// Next unfired point index into the matching entry of
+    /// `automation_tracks`, parallel by position.
+    automation_next_index: Vec<usize>,
+}
+
+impl MidiFilePlayer {
+    /// Parses a `.mid` file into a flat, tick-ordered event timeline.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes = std::fs::read(path)?;
+        let smf = midly::Smf::parse(&bytes)?;
+
+        let ticks_per_quarter = match smf.header.timing {
+            midly::Timing::Metrical(ticks) => ticks.as_int(),
+            midly::Timing::Timecode(..) => 480,
+        };
+
+        let mut events = Vec::new();
+        let mut tempo_map = vec![(0u64, 500_000u32)];
+
+        for track in &smf.tracks {
+            let mut tick = 0u64;
+            for event in track {
+                tick += event.delta.as_int() as u64;
+                match event.kind {
+                    midly::TrackEventKind::Midi { channel, message } => {
+                        let channel = channel.as_int();
+                        let playback_event = match message {
+                            midly::MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+                                Some(PlaybackEvent::NoteOn {
+                                    channel,
+                                    note: key.as_int(),
+                                    velocity: vel.as_int(),
+                                })
+                            }
+                            midly::MidiMessage::NoteOn { key, .. } | midly::MidiMessage::NoteOff { key, .. } => {
+                                Some(PlaybackEvent::NoteOff { channel, note: key.as_int() })
+                            }
+                            midly::MidiMessage::Controller { controller, value } => Some(PlaybackEvent::ControlChange {
+                                channel,
+                                controller: controller.as_int(),
+                                value: value.as_int(),
+                            }),
+                            _ => None,
+                        };
+                        if let Some(playback_event) = playback_event {
+                            events.push(TimedEvent { tick, event: playback_event });
+                        }
+                    }
+                    midly::TrackEventKind::Meta(midly::MetaMessage::Tempo(microseconds_per_quarter)) => {
+                        tempo_map.push((tick, microseconds_per_quarter.as_int()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        events.sort_by_key(|timed| timed.tick);
+        tempo_map.sort_by_key(|(tick, _)| *tick);
+
+        Ok(Self {
+            events,
+            ticks_per_quarter,
+            tempo_map,
+            playing: false,
+            loop_playback: false,
+            current_tick: 0,
+            muted_channels: HashSet::new(),
+            tick_accumulator: 0.0,
+            last_event_index: 0,
+            automation_tracks: Vec::new(),
+            automation_next_index: Vec::new(),
+        })
+    }
+
+    /// Replaces the automation tracks fired alongside this file's MIDI
+    /// events, resetting playback position for all of them.
+    pub fn set_automation_tracks(&mut self, tracks: Vec<AutomationTrack>) {
+        self.automation_next_index = vec![0; tracks.len()];
+        self.automation_tracks = tracks;
+    }
+
+    pub fn automation_tracks(&self) -> &[AutomationTrack] {
+        &self.automation_tracks
+    }
+
+    pub fn automation_tracks_mut(&mut self) -> &mut [AutomationTrack] {
+        &mut self.automation_tracks
+    }
+
+    pub fn mute_channel(&mut self, channel: u8, muted: bool) {
+        if muted {
+            self.muted_channels.insert(channel);
+        } else {
+            self.muted_channels.remove(&channel);
+        }
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.current_tick = 0;
+        self.last_event_index = 0;
+        self.tick_accumulator = 0.0;
+        self.automation_next_index.iter_mut().for_each(|index| *index = 0);
+    }
+
+    /// Microseconds per quarter note in effect at `tick`, per the tempo map.
+    fn tempo_at(&self, tick: u64) -> u32 {
+        self.tempo_map
+            .iter()
+            .take_while(|(at, _)| *at <= tick)
+            .last()
+            .map(|(_, tempo)| *tempo)
+            .unwrap_or(500_000)
+    }
+
+    /// Advances playback by `sample_time` seconds, delivering every event
+    /// crossed along the way to `on_event`. No-op while paused or stopped.
+    pub fn tick(&mut self, sample_time: f32, mut on_event: impl FnMut(PlaybackEvent)) {
+        if !self.playing {
+            return;
+        }
+
+        let microseconds_per_quarter = self.tempo_at(self.current_tick) as f64;
+        let ticks_per_second = (self.ticks_per_quarter as f64 * 1_000_000.0) / microseconds_per_quarter;
+        self.tick_accumulator += sample_time as f64 * ticks_per_second;
+
+        let advance_ticks = self.tick_accumulator.floor();
+        self.tick_accumulator -= advance_ticks;
+        self.current_tick += advance_ticks as u64;
+
+        while self.last_event_index < self.events.len() && self.events[self.last_event_index].tick <= self.current_tick {
+            let timed = self.events[self.last_event_index];
+            self.last_event_index += 1;
+            let channel = match timed.event {
+                PlaybackEvent::NoteOn { channel, .. }
+                | PlaybackEvent::NoteOff { channel, .. }
+                | PlaybackEvent::ControlChange { channel, .. } => channel,
+                PlaybackEvent::Automation { .. } => {
+                    unreachable!("automation events are fired separately, never stored in `events`")
+                }
+            };
+            if !self.muted_channels.contains(&channel) {
+                on_event(timed.event);
+            }
+        }
+
+        for (track, next_index) in self.automation_tracks.iter().zip(self.automation_next_index.iter_mut()) {
+            if !track.enabled {
+                continue;
+            }
+            while *next_index < track.points.len() && track.points[*next_index].tick <= self.current_tick {
+                let point = track.points[*next_index];
+                *next_index += 1;
+                on_event(PlaybackEvent::Automation { parameter: track.parameter, value: point.value });
+            }
+        }
+
+        if self.last_event_index >= self.events.len() {
+            if self.loop_playback {
+                self.current_tick = 0;
+                self.last_event_index = 0;
+                self.tick_accumulator = 0.0;
+                self.automation_next_index.iter_mut().for_each(|index| *index = 0);
+            } else {
+                self.playing = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use midir::{Ignore, MidiInput, MidiInputConnection};
+
+/// A parsed MIDI channel voice message, stripped of its raw status byte.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MidiMessage {
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8 },
+    ControlChange { channel: u8, controller: u8, value: u8 },
+    PitchBend { channel: u8, value: i16 },
+    /// Polyphonic (per-note) aftertouch, as used by MPE controllers for
+    /// per-note pressure instead of channel-wide aftertouch.
+    PolyphonicAftertouch { channel: u8, note: u8, pressure: u8 },
+    /// A System Exclusive message, `0xF0` through the terminating `0xF7`
+    /// inclusive, passed through unparsed for `SysExHandler` to decode.
+    SysEx(Vec<u8>),
+}
+
+/// MIDI Polyphonic Expression state for a single member channel: each note
+/// gets its own pitch bend and pressure, layered on top of the note's base
+/// pitch and velocity.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MpeNoteState {
+    pub pitch_bend_semitones: f32,
+    pub pressure: f32,
+}
+
+/// Tracks MPE note state across the zone's member channels (2-16 by MPE
+/// convention, with channel 1 reserved as the master channel).
+#[derive(Debug, Clone, Default)]
+pub struct MpeZone {
+    note_states: std::collections::HashMap<(u8, u8), MpeNoteState>,
+    /// Semitones of bend for a full pitch-bend-wheel deflection, per the
+    /// zone's MPE configuration message (defaults to the common +/-48).
+    pub pitch_bend_range_semitones: f32,
+}
+
+impl MpeZone {
+    pub fn new() -> Self {
+        Self {
+            note_states: std::collections::HashMap::new(),
+            pitch_bend_range_semitones: 48.0,
+        }
+    }
+
+    /// Updates per-note pitch bend (from a channel pitch bend message, per
+    /// MPE convention where each member channel carries exactly one note).
+    pub fn set_pitch_bend(&mut self, channel: u8, note: u8, bend_value: i16) {
+        let semitones = (bend_value as f32 / 8192.0) * self.pitch_bend_range_semitones;
+        self.note_states.entry((channel, note)).or_default().pitch_bend_semitones = semitones;
+    }
+
+    pub fn set_pressure(&mut self, channel: u8, note: u8, pressure: u8) {
+        self.note_states.entry((channel, note)).or_default().pressure = pressure as f32 / 127.0;
+    }
+
+    pub fn state_for_note(&self, channel: u8, note: u8) -> MpeNoteState {
+        self.note_states.get(&(channel, note)).copied().unwrap_or_default()
+    }
+
+    pub fn clear_note(&mut self, channel: u8, note: u8) {
+        self.note_states.remove(&(channel, note));
+    }
+}
+
+impl MidiMessage {
+    /// Parses a raw MIDI byte sequence, or `None` for messages we don't
+    /// route (Program Change, channel aftertouch, and system messages other
+    /// than SysEx).
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let status = *bytes.first()?;
+        if status == 0xF0 {
+            return Some(MidiMessage::SysEx(bytes.to_vec()));
+        }
+        let channel = status & 0x0F;
+        match status & 0xF0 {
+            0x90 if bytes.get(2).copied().unwrap_or(0) > 0 => Some(MidiMessage::NoteOn {
+                channel,
+                note: bytes[1],
+                velocity: bytes[2],
+            }),
+            // A note-on with velocity 0 is conventionally a note-off.
+            0x90 | 0x80 => Some(MidiMessage::NoteOff {
+                channel,
+                note: *bytes.get(1)?,
+            }),
+            0xB0 => Some(MidiMessage::ControlChange {
+                channel,
+                controller: *bytes.get(1)?,
+                value: *bytes.get(2)?,
+            }),
+            0xA0 => Some(MidiMessage::PolyphonicAftertouch {
+                channel,
+                note: *bytes.get(1)?,
+                pressure: *bytes.get(2)?,
+            }),
+            0xE0 => {
+                let lsb = *bytes.get(1)? as i16;
+                let msb = *bytes.get(2)? as i16;
+                Some(MidiMessage::PitchBend {
+                    channel,
+                    value: ((msb << 7) | lsb) - 8192,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Maps MIDI input channels (1-16) to the oscillator-template indices in
+/// `Synth::oscillator_templates` that should respond to them. Channels not
+/// present in the map route to every oscillator, preserving the previous
+/// single-channel-for-everything behavior.
+#[derive(Debug, Clone, Default)]
+pub struct ChannelRouting {
+    routes: std::collections::HashMap<u8, Vec<usize>>,
+}
+
+impl ChannelRouting {
+    pub fn route_channel_to_oscillators(&mut self, channel: u8, oscillator_indices: Vec<usize>) {
+        self.routes.insert(channel, oscillator_indices);
+    }
+
+    /// Returns the oscillator-template indices that `channel` should drive,
+    /// or `None` to mean "all of them".
+    pub fn oscillators_for_channel(&self, channel: u8) -> Option<&[usize]> {
+        self.routes.get(&channel).map(Vec::as_slice)
+    }
+}
+
+/// Lists the names of the currently available MIDI input ports, for a
+/// port-selector UI to populate before calling `connect`.
+pub fn available_ports() -> Vec<String> {
+    let Ok(input) = MidiInput::new("AudioTheorem") else { return Vec::new() };
+    input.ports().iter().filter_map(|port| input.port_name(port).ok()).collect()
+}
+
+/// Opens a connection to `port_name` and forwards parsed `MidiMessage`s to
+/// `on_message`. Held for the lifetime of the connection.
+pub fn connect(
+    port_name: &str,
+    mut on_message: impl FnMut(MidiMessage) + Send + 'static,
+) -> Result<MidiInputConnection<()>, Box<dyn std::error::Error>> {
+    let mut input = MidiInput::new("AudioTheorem")?;
+    input.ignore(Ignore::None);
+
+    let ports = input.ports();
+    let port = ports
+        .iter()
+        .find(|p| input.port_name(p).map(|n| n == port_name).unwrap_or(false))
+        .ok_or("MIDI port not found")?;
+
+    let connection = input.connect(
+        port,
+        "audio-theorem-input",
+        move |_timestamp, bytes, _| {
+            if let Some(message) = MidiMessage::parse(bytes) {
+                on_message(message);
+            }
+        },
+        (),
+    )?;
+
+    Ok(connection)
+}
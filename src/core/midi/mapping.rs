@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A synth parameter that can be bound to a MIDI CC via "MIDI learn".
+///
+/// This only covers parameters that exist as real, addressable state
+/// elsewhere in the tree today. It has no LFO rate/depth targets:
+/// `core::oscillator::lfo::Lfo` exists, but no `Oscillator` or `Synth`
+/// owns one, so there is no live LFO rate or depth to write a CC into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MidiControlTarget {
+    MasterVolume,
+    OscillatorVolume(usize),
+    OscillatorPan(usize),
+    OscillatorDetune(usize),
+    FilterCutoff(usize),
+    FilterResonance(usize),
+    /// Scan position (0-based frame index) into a `CustomWavetable`'s
+    /// multi-frame sample data.
+    WavetablePosition(usize),
+    /// Wet/dry mix for the effects-chain slot at this index.
+    EffectWetDry(usize),
+    /// One of `Synth::macros`' eight performance macro knobs.
+    Macro(usize),
+}
+
+impl MidiControlTarget {
+    pub fn label(&self) -> String {
+        match self {
+            MidiControlTarget::MasterVolume => "Master Volume".to_string(),
+            MidiControlTarget::OscillatorVolume(i) => format!("Osc {} Volume", i + 1),
+            MidiControlTarget::OscillatorPan(i) => format!("Osc {} Pan", i + 1),
+            MidiControlTarget::OscillatorDetune(i) => format!("Osc {} Detune", i + 1),
+            MidiControlTarget::FilterCutoff(i) => format!("Osc {} Filter Cutoff", i + 1),
+            MidiControlTarget::FilterResonance(i) => format!("Osc {} Filter Resonance", i + 1),
+            MidiControlTarget::WavetablePosition(i) => format!("Osc {} Wavetable Position", i + 1),
+            MidiControlTarget::EffectWetDry(i) => format!("Effect Slot {} Wet/Dry", i + 1),
+            MidiControlTarget::Macro(i) => format!("Macro {}", i + 1),
+        }
+    }
+}
+
+fn default_in_min() -> u8 {
+    0
+}
+
+fn default_in_max() -> u8 {
+    127
+}
+
+fn default_out_min() -> f32 {
+    0.0
+}
+
+fn default_out_max() -> f32 {
+    1.0
+}
+
+/// One MIDI-learn binding: the target it drives, plus the input/output
+/// range remapping hardware controllers often need (a fader whose useful
+/// travel is only part of its 0-127 range, or an axis that should run
+/// backwards). `#[serde(default = ...)]` on every range field means a
+/// binding saved before this remapping existed loads as a plain
+/// `0..127 -> 0.0..1.0`, non-inverted pass-through — the same behavior
+/// `process_midi_value` had before these fields existed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MidiMappingEntry {
+    pub target: MidiControlTarget,
+    #[serde(default = "default_in_min")]
+    pub in_min: u8,
+    #[serde(default = "default_in_max")]
+    pub in_max: u8,
+    #[serde(default = "default_out_min")]
+    pub out_min: f32,
+    #[serde(default = "default_out_max")]
+    pub out_max: f32,
+    #[serde(default)]
+    pub invert: bool,
+}
+
+impl MidiMappingEntry {
+    pub fn new(target: MidiControlTarget) -> Self {
+        Self {
+            target,
+            in_min: default_in_min(),
+            in_max: default_in_max(),
+            out_min: default_out_min(),
+            out_max: default_out_max(),
+            invert: false,
+        }
+    }
+
+    /// Linearly maps a raw `0..127` CC value from `in_min..in_max` to
+    /// `out_min..out_max`, clamping out-of-range input first, then
+    /// flipping the result within `out_min..out_max` if `invert` is set.
+    pub fn process_midi_value(&self, raw_value: u8) -> f32 {
+        let in_min = self.in_min as f32;
+        let in_max = (self.in_max as f32).max(in_min + 1.0);
+        let clamped = (raw_value as f32).clamp(in_min, in_max);
+        let t = (clamped - in_min) / (in_max - in_min);
+        let out = self.out_min + t * (self.out_max - self.out_min);
+        if self.invert {
+            self.out_max - (out - self.out_min)
+        } else {
+            out
+        }
+    }
+}
+
+/// Binds `(channel, controller)` pairs to `MidiMappingEntry`s, so an
+/// incoming CC can be routed to the parameter the user "learned" it to,
+/// remapped to that parameter's useful range.
+#[derive(Debug, Clone, Default)]
+pub struct MidiLearnMap {
+    bindings: HashMap<(u8, u8), MidiMappingEntry>,
+}
+
+impl MidiLearnMap {
+    pub fn bind(&mut self, channel: u8, controller: u8, target: MidiControlTarget) {
+        self.bindings.insert((channel, controller), MidiMappingEntry::new(target));
+    }
+
+    pub fn unbind(&mut self, channel: u8, controller: u8) {
+        self.bindings.remove(&(channel, controller));
+    }
+
+    pub fn target_for(&self, channel: u8, controller: u8) -> Option<MidiControlTarget> {
+        self.bindings.get(&(channel, controller)).map(|entry| entry.target)
+    }
+
+    pub fn mapping_for(&self, channel: u8, controller: u8) -> Option<&MidiMappingEntry> {
+        self.bindings.get(&(channel, controller))
+    }
+
+    pub fn mapping_mut(&mut self, channel: u8, controller: u8) -> Option<&mut MidiMappingEntry> {
+        self.bindings.get_mut(&(channel, controller))
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (&(u8, u8), &MidiMappingEntry)> {
+        self.bindings.iter()
+    }
+}
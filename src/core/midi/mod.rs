@@ -0,0 +1,64 @@
+pub mod activity_log;
+pub mod file_player;
+pub mod input;
+pub mod mapping;
+pub mod sysex;
+
+use std::collections::HashMap;
+
+pub use mapping::{MidiControlTarget, MidiLearnMap, MidiMappingEntry};
+
+/// Owns cross-cutting MIDI processing state (CC slew limiting and the MIDI
+/// learn target bindings) shared across whichever input connections are
+/// active.
+#[derive(Debug, Clone, Default)]
+pub struct MidiSystem {
+    /// Last smoothed value per `(channel, controller)`: `0.0..1.0` from
+    /// `apply_midi_cc`, or the binding's output range from `resolve_cc`.
+    smoothed_cc_values: HashMap<(u8, u8), f32>,
+    /// How far a smoothed CC value can move per call, in the same units as
+    /// that value. Smaller is smoother but slower to respond.
+    pub slew_rate: f32,
+    /// User-configured "MIDI learn" bindings from CC to synth parameter.
+    pub learn_map: MidiLearnMap,
+}
+
+impl MidiSystem {
+    pub fn new(slew_rate: f32) -> Self {
+        Self {
+            smoothed_cc_values: HashMap::new(),
+            slew_rate,
+            learn_map: MidiLearnMap::default(),
+        }
+    }
+
+    /// Applies a raw MIDI CC value through the slew limiter to eliminate
+    /// "zipper noise" from coarse 7-bit CC steps, returning the smoothed
+    /// value (`0.0..1.0`).
+    pub fn apply_midi_cc(&mut self, channel: u8, controller: u8, raw_value: u8) -> f32 {
+        self.slew(channel, controller, raw_value as f32 / 127.0)
+    }
+
+    /// Moves this `(channel, controller)`'s smoothed value toward `target`
+    /// by at most `slew_rate`, to eliminate "zipper noise" from coarse
+    /// 7-bit CC steps.
+    fn slew(&mut self, channel: u8, controller: u8, target: f32) -> f32 {
+        let key = (channel, controller);
+        let current = *self.smoothed_cc_values.get(&key).unwrap_or(&target);
+        let delta = (target - current).clamp(-self.slew_rate, self.slew_rate);
+        let smoothed = current + delta;
+        self.smoothed_cc_values.insert(key, smoothed);
+        smoothed
+    }
+
+    /// If this CC has been "learned" to a target, maps `raw_value` through
+    /// that binding's `MidiMappingEntry::process_midi_value` (applying its
+    /// configured range and inversion) and slew-limits the result, so the
+    /// caller can apply it directly to the matching synth parameter.
+    pub fn resolve_cc(&mut self, channel: u8, controller: u8, raw_value: u8) -> Option<(MidiControlTarget, f32)> {
+        let mapping = self.learn_map.mapping_for(channel, controller)?;
+        let target = mapping.target;
+        let mapped_value = mapping.process_midi_value(raw_value);
+        Some((target, self.slew(channel, controller, mapped_value)))
+    }
+}
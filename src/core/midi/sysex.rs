@@ -0,0 +1,39 @@
+use crate::core::synth::preset::SynthPreset;
+
+/// AudioTheorem's SysEx manufacturer ID prefix (unregistered/development
+/// range), used to recognize our own bulk preset dumps and ignore anyone
+/// else's SysEx traffic on the same port.
+pub const MANUFACTURER_ID: [u8; 2] = [0x00, 0x41];
+
+/// Decodes and encodes AudioTheorem-specific SysEx messages, currently just
+/// bulk preset dumps. Presets are carried as plain UTF-8 JSON (matching
+/// `SynthPreset::to_json`/`from_json`) with each byte's high bit cleared,
+/// since SysEx data bytes must be 7-bit; this is lossy for any non-ASCII
+/// preset metadata, which is an accepted limitation rather than a full
+/// 8-to-7-bit packing scheme.
+pub struct SysExHandler;
+
+impl SysExHandler {
+    /// Decodes `data` (a full SysEx message, `0xF0`..`0xF7` inclusive) into
+    /// a preset if it's an AudioTheorem bulk preset dump, or `None` if it's
+    /// not ours or doesn't parse.
+    pub fn handle(data: &[u8]) -> Option<SynthPreset> {
+        let payload = data.strip_prefix(&[0xF0])?;
+        let payload = payload.strip_prefix(&MANUFACTURER_ID)?;
+        let payload = payload.strip_suffix(&[0xF7]).unwrap_or(payload);
+        let json = String::from_utf8(payload.to_vec()).ok()?;
+        SynthPreset::from_json(&json).ok()
+    }
+
+    /// Encodes `preset` as an AudioTheorem bulk preset dump SysEx message,
+    /// ready to send to a connected MIDI output.
+    pub fn encode_preset_dump(preset: &SynthPreset) -> Vec<u8> {
+        let json = preset.to_json().unwrap_or_default();
+        let mut message = Vec::with_capacity(json.len() + 4);
+        message.push(0xF0);
+        message.extend_from_slice(&MANUFACTURER_ID);
+        message.extend(json.bytes().map(|byte| byte & 0x7F));
+        message.push(0xF7);
+        message
+    }
+}
@@ -0,0 +1,14 @@
+pub mod analyzer;
+pub mod drums;
+pub mod effects;
+pub mod macros;
+pub mod midi;
+pub mod osc;
+pub mod sequencer;
+pub mod session;
+pub mod tuning;
+pub mod voice;
+pub mod oscillator;
+pub mod synth;
+pub mod theory;
+pub mod visualization;
@@ -0,0 +1,36 @@
+use rosc::{OscMessage, OscPacket};
+use std::net::UdpSocket;
+
+/// Runs an OSC server on `bind_addr` (e.g. `"0.0.0.0:9000"`) on a dedicated
+/// thread, forwarding each decoded message to `on_message` for the caller
+/// to route into the app's existing lock-free channels.
+pub fn spawn_osc_server(
+    bind_addr: &str,
+    mut on_message: impl FnMut(OscMessage) + Send + 'static,
+) -> std::io::Result<()> {
+    let socket = UdpSocket::bind(bind_addr)?;
+    std::thread::spawn(move || {
+        let mut buffer = [0u8; rosc::decoder::MTU];
+        loop {
+            let Ok((size, _)) = socket.recv_from(&mut buffer) else {
+                continue;
+            };
+            let Ok((_, packet)) = rosc::decoder::decode_udp(&buffer[..size]) else {
+                continue;
+            };
+            if let OscPacket::Message(message) = packet {
+                on_message(message);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Known OSC addresses the rest of the app should route on. Kept as plain
+/// string constants rather than an enum since OSC addresses are
+/// hierarchical paths, not a closed set.
+pub mod addresses {
+    pub const NOTE_ON: &str = "/note/on";
+    pub const NOTE_OFF: &str = "/note/off";
+    pub const OSCILLATOR_VOLUME: &str = "/oscillator/{index}/volume";
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// A single harmonic partial in an `Additive` combination: a multiple of
+/// the fundamental frequency plus its own amplitude.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HarmonicPartial {
+    pub harmonic_number: u32,
+    pub amplitude: f32,
+}
+
+/// How a voice's oscillators are combined into one output sample.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum OscillatorCombinationMode {
+    /// Each oscillator contributes its own independent waveform, summed.
+    #[default]
+    Mix,
+    /// Oscillators are summed as weighted sine partials at integer
+    /// multiples of the fundamental, ignoring each oscillator's own
+    /// waveform setting.
+    Additive(Vec<HarmonicPartial>),
+}
+
+impl OscillatorCombinationMode {
+    /// Renders one additive sample at `phase` (the fundamental's phase,
+    /// `0.0..1.0`). Returns `0.0` for `Mix`, since that mode is rendered by
+    /// summing each `Oscillator` instead.
+    pub fn sample_additive(&self, phase: f32) -> f32 {
+        match self {
+            OscillatorCombinationMode::Mix => 0.0,
+            OscillatorCombinationMode::Additive(partials) => partials
+                .iter()
+                .map(|partial| {
+                    let partial_phase = (phase * partial.harmonic_number as f32) % 1.0;
+                    (partial_phase * std::f32::consts::TAU).sin() * partial.amplitude
+                })
+                .sum(),
+        }
+    }
+}
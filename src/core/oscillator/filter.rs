@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FilterType {
+    LowPass,
+    HighPass,
+    BandPass,
+    /// Passes every frequency at unity gain, only shifting phase. Used as
+    /// the building block of `core::effects::phaser::Phaser`; has no
+    /// effect on `magnitude_response_db` since an allpass has none.
+    AllPass,
+}
+
+/// A one-pole/two-pole style resonant filter applied after the oscillator
+/// mix. `cutoff_hz` and `resonance` are the only knobs the UI exposes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Filter {
+    pub filter_type: FilterType,
+    pub cutoff_hz: f32,
+    pub resonance: f32,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            filter_type: FilterType::LowPass,
+            cutoff_hz: 2_000.0,
+            resonance: 0.7,
+        }
+    }
+}
+
+/// A first-order (6 dB/octave) low-pass: an exponential moving average that
+/// slews `state` toward each new `input` at a rate set by `cutoff_hz`. The
+/// cheapest filter with real per-sample memory, as opposed to
+/// `Filter::magnitude_response_db`, which only describes a curve for the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct OnePoleLowPass {
+    state: f32,
+}
+
+impl OnePoleLowPass {
+    pub fn process(&mut self, input: f32, cutoff_hz: f32, sample_rate: f32) -> f32 {
+        let alpha = one_pole_alpha(cutoff_hz, sample_rate);
+        self.state += alpha * (input - self.state);
+        self.state
+    }
+}
+
+/// A first-order (6 dB/octave) high-pass, built as `input` minus its own
+/// low-pass component.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct OnePoleHighPass {
+    low_pass: OnePoleLowPass,
+}
+
+impl OnePoleHighPass {
+    pub fn process(&mut self, input: f32, cutoff_hz: f32, sample_rate: f32) -> f32 {
+        input - self.low_pass.process(input, cutoff_hz, sample_rate)
+    }
+}
+
+/// Per-sample smoothing coefficient for a one-pole filter at `cutoff_hz`,
+/// derived from the standard RC/exponential-smoothing correspondence.
+fn one_pole_alpha(cutoff_hz: f32, sample_rate: f32) -> f32 {
+    1.0 - (-2.0 * std::f32::consts::PI * cutoff_hz.max(1.0) / sample_rate).exp()
+}
+
+/// Per-oscillator filter state, the stateful counterpart to `Filter`'s
+/// UI-only response curve. Only `FilterType::LowPass`/`HighPass` have a real
+/// one-pole implementation so far; `BandPass`/`AllPass` pass the signal
+/// through unfiltered until a proper biquad replaces this (see
+/// `Oscillator::sync_filter_state`, which rebuilds this to match
+/// `Filter::filter_type` whenever it changes).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FilterState {
+    LowPass(OnePoleLowPass),
+    HighPass(OnePoleHighPass),
+    Bypass,
+}
+
+impl FilterState {
+    /// Builds the `FilterState` variant matching `filter_type`, starting
+    /// from silence.
+    pub fn for_type(filter_type: FilterType) -> Self {
+        match filter_type {
+            FilterType::LowPass => FilterState::LowPass(OnePoleLowPass::default()),
+            FilterType::HighPass => FilterState::HighPass(OnePoleHighPass::default()),
+            FilterType::BandPass | FilterType::AllPass => FilterState::Bypass,
+        }
+    }
+
+    pub fn process(&mut self, input: f32, cutoff_hz: f32, sample_rate: f32) -> f32 {
+        match self {
+            FilterState::LowPass(filter) => filter.process(input, cutoff_hz, sample_rate),
+            FilterState::HighPass(filter) => filter.process(input, cutoff_hz, sample_rate),
+            FilterState::Bypass => input,
+        }
+    }
+}
+
+impl Default for FilterState {
+    fn default() -> Self {
+        FilterState::for_type(FilterType::LowPass)
+    }
+}
+
+impl Filter {
+    /// Approximate magnitude response in dB at `frequency_hz`, used only
+    /// for drawing the response curve (not for audio processing).
+    pub fn magnitude_response_db(&self, frequency_hz: f32) -> f32 {
+        if self.filter_type == FilterType::AllPass {
+            return 0.0;
+        }
+        let ratio = frequency_hz / self.cutoff_hz.max(1.0);
+        let resonance_bump = if (0.5..2.0).contains(&ratio) {
+            self.resonance * 6.0
+        } else {
+            0.0
+        };
+
+        let rolloff = match self.filter_type {
+            FilterType::LowPass => -12.0 * (ratio.max(1.0)).log2(),
+            FilterType::HighPass => -12.0 * (1.0 / ratio.clamp(0.001, 1.0)).log2(),
+            FilterType::BandPass => -12.0 * (ratio.max(1.0 / ratio)).log2(),
+            FilterType::AllPass => 0.0,
+        };
+
+        (rolloff + resonance_bump).max(-60.0)
+    }
+
+    /// Samples the response curve at `count` points log-spaced between
+    /// `min_hz` and `max_hz`, returning `(frequency_hz, magnitude_db)` pairs
+    /// for plotting.
+    pub fn response_curve(&self, min_hz: f32, max_hz: f32, count: usize) -> Vec<(f32, f32)> {
+        let log_min = min_hz.max(1.0).ln();
+        let log_max = max_hz.ln();
+        (0..count)
+            .map(|i| {
+                let t = i as f32 / (count - 1).max(1) as f32;
+                let frequency_hz = (log_min + (log_max - log_min) * t).exp();
+                (frequency_hz, self.magnitude_response_db(frequency_hz))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use super::Waveform;
+
+/// Beat-value fractions offered by the LFO sync UI, in `(numerator,
+/// denominator, label)` form. `T` suffixes are triplets (denominator
+/// multiplied by 1.5 relative to the straight value).
+pub const COMMON_BEAT_FRACTIONS: [(u32, u32, &str); 8] = [
+    (1, 32, "1/32"),
+    (1, 16, "1/16"),
+    (1, 12, "1/8T"),
+    (1, 8, "1/8"),
+    (1, 6, "1/4T"),
+    (1, 4, "1/4"),
+    (1, 2, "1/2"),
+    (1, 1, "1/1"),
+];
+
+/// How an `Lfo`'s rate is derived: a free-running Hz value, or a fraction
+/// of the host tempo's beat length.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LfoRate {
+    /// Free-running rate in Hz, independent of tempo.
+    Hertz(f32),
+    /// `numerator / denominator` of a beat, e.g. `BeatFraction(1, 8)` is a
+    /// 1/8 note. Resolved against `tempo_bpm` in `Lfo::advance`.
+    BeatFraction(u32, u32),
+}
+
+impl LfoRate {
+    /// The effective frequency in Hz, resolving `BeatFraction` against
+    /// `tempo_bpm`: one beat is `tempo_bpm / 60.0` Hz, scaled by the
+    /// fraction of a beat this rate represents.
+    pub fn frequency_hz(&self, tempo_bpm: f32) -> f32 {
+        match *self {
+            LfoRate::Hertz(hz) => hz,
+            LfoRate::BeatFraction(numerator, denominator) => {
+                let beats_per_second = tempo_bpm / 60.0;
+                beats_per_second * (numerator as f32 / denominator.max(1) as f32)
+            }
+        }
+    }
+
+    /// Note-value notation for sync mode (e.g. `"1/8"`), or a Hz readout
+    /// for free-running mode, for display in place of a bare Hz number.
+    pub fn label(&self) -> String {
+        match *self {
+            LfoRate::Hertz(hz) => format!("{hz:.2} Hz"),
+            LfoRate::BeatFraction(numerator, denominator) => COMMON_BEAT_FRACTIONS
+                .iter()
+                .find(|(n, d, _)| *n == numerator && *d == denominator)
+                .map(|(_, _, label)| label.to_string())
+                .unwrap_or_else(|| format!("{numerator}/{denominator}")),
+        }
+    }
+}
+
+impl Default for LfoRate {
+    fn default() -> Self {
+        LfoRate::Hertz(2.0)
+    }
+}
+
+/// A low-frequency oscillator for modulating a synth parameter. Not yet
+/// wired into `Oscillator`/`Synth` — there is no modulation matrix routing
+/// an `Lfo`'s `value()` to a destination parameter yet — but the rate
+/// model, tempo sync, and beat-aligned retrigger are complete and ready
+/// for that wiring.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lfo {
+    pub rate: LfoRate,
+    pub waveform: Waveform,
+    phase: f32,
+}
+
+impl Lfo {
+    pub fn new(rate: LfoRate, waveform: Waveform) -> Self {
+        Self { rate, waveform, phase: 0.0 }
+    }
+
+    /// Advances `phase` by one sample at `rate.frequency_hz(tempo_bpm)`,
+    /// wrapping into `0.0..1.0`. `tempo_bpm` is ignored when `rate` is
+    /// `LfoRate::Hertz`.
+    pub fn advance(&mut self, tempo_bpm: f32, sample_rate: f32) {
+        self.phase += self.rate.frequency_hz(tempo_bpm) / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+        }
+    }
+
+    /// Resets `phase` to `beat_phase` (the host's current position within
+    /// the beat this LFO syncs to, `0.0..1.0`), so a note-on retrigger
+    /// lands in step with the beat instead of restarting from `0.0`
+    /// regardless of where the beat currently is. Free-running (`Hertz`)
+    /// LFOs ignore `beat_phase` and always retrigger to `0.0`, matching
+    /// the non-tempo-aware behavior they're expected to have.
+    pub fn retrigger(&mut self, beat_phase: f32) {
+        self.phase = match self.rate {
+            LfoRate::Hertz(_) => 0.0,
+            LfoRate::BeatFraction(..) => beat_phase.rem_euclid(1.0),
+        };
+    }
+
+    /// The LFO's current output, `-1.0..1.0`.
+    pub fn value(&mut self) -> f32 {
+        self.waveform.sample(self.phase)
+    }
+}
+
+impl Default for Lfo {
+    fn default() -> Self {
+        Self::new(LfoRate::default(), Waveform::Sine)
+    }
+}
@@ -0,0 +1,332 @@
+pub mod combination;
+pub mod filter;
+pub mod lfo;
+pub mod processor;
+pub mod waveform;
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub use combination::{HarmonicPartial, OscillatorCombinationMode};
+pub use filter::{Filter, FilterState, FilterType};
+pub use lfo::{Lfo, LfoRate};
+pub use processor::process_oscillator_sample;
+pub use waveform::Waveform;
+
+/// How strongly note-on velocity affects an oscillator's output level.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum VelocitySensitivity {
+    /// Velocity is ignored; the oscillator always plays at full level.
+    None,
+    /// Output gain scales linearly with velocity.
+    #[default]
+    Linear,
+    /// Output gain scales with `velocity.powf(exponent)`, normalized to
+    /// `0.0..1.0`. `exponent > 1.0` makes soft playing quieter and gives
+    /// more headroom to dynamic playing; `exponent < 1.0` flattens it out.
+    Exponential(f32),
+}
+
+impl VelocitySensitivity {
+    /// Converts a velocity amplitude (0.0-1.0, already passed through the
+    /// synth-wide `VelocityLaw`) into this oscillator's gain multiplier.
+    pub fn gain(&self, velocity_amplitude: f32) -> f32 {
+        let normalized = velocity_amplitude.clamp(0.0, 1.0);
+        match self {
+            VelocitySensitivity::None => 1.0,
+            VelocitySensitivity::Linear => normalized,
+            VelocitySensitivity::Exponential(exponent) => normalized.powf(*exponent),
+        }
+    }
+}
+
+/// How the built-in ring modulator's frequency is derived.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RingModMode {
+    /// A fixed frequency in Hz (0.1-5000), independent of the note played.
+    Fixed(f32),
+    /// A multiple of the voice's note frequency, so the modulator tracks
+    /// pitch along with the carrier.
+    Ratio(f32),
+}
+
+impl Default for RingModMode {
+    fn default() -> Self {
+        RingModMode::Fixed(1.0)
+    }
+}
+
+/// Whether a newly triggered voice's oscillators restart their phase from
+/// `0.0`, or keep running from wherever the ensemble already is. Always
+/// resetting can cause frequency-dependent constructive interference when
+/// many notes start in sync; never resetting avoids that at the cost of
+/// phase artifacts on a note retriggered in isolation. Used as `Synth`'s
+/// master-level fallback; there is no per-oscillator override.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum OscillatorPhaseReset {
+    #[default]
+    AlwaysReset,
+    NeverReset,
+}
+
+/// The top of the MIDI note range, used as `Oscillator::midi_note_high`'s
+/// default so an oscillator with no configured range still responds to
+/// every note.
+fn default_midi_note_high() -> u8 {
+    127
+}
+
+/// A single oscillator within a `Synth` voice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Oscillator {
+    pub waveform: Waveform,
+    pub phase: f32,
+    pub detune_semitones: f32,
+    /// Playback-rate shift applied only to `Waveform::CustomSample`, on top
+    /// of the rate implied by the current MIDI note. Unlike `detune_semitones`
+    /// (which retunes the note itself), this lets a sample's pitch move
+    /// independently of the note it's triggered on, e.g. keeping a vocal
+    /// chop's formants closer to their original recording.
+    pub pitch_shift_semitones: f32,
+    pub volume: f32,
+    pub enabled: bool,
+    pub velocity_sensitivity: VelocitySensitivity,
+    pub filter: Filter,
+    /// Stateful one-pole filter matching `filter.filter_type`, applied to
+    /// this oscillator's output in `process_oscillator_sample`. Kept in
+    /// sync with `filter.filter_type` by `sync_filter_state`, since
+    /// switching filter types mid-note needs a freshly built `FilterState`
+    /// rather than reinterpreting old state under the new type.
+    #[serde(default)]
+    filter_state: FilterState,
+    pub ring_mod_enabled: bool,
+    pub ring_mod_mode: RingModMode,
+    pub ring_mod_waveform: Waveform,
+    /// The modulator's own running phase, advanced alongside `phase`.
+    ring_mod_phase: f32,
+    /// Stereo position for `Synth::get_stereo_sample`: -1.0 is full left,
+    /// 0.0 is center, 1.0 is full right.
+    pub pan: f32,
+    /// How much louder-velocity notes open this oscillator's filter cutoff,
+    /// in octaves at full velocity (0.0 = no effect). Modeled on piano and
+    /// brass instruments, where harder playing brightens the tone as well
+    /// as raising its level. Applied once per note-on in `Synth::trigger_voice`.
+    pub velocity_to_filter_cutoff: f32,
+    /// Lowest MIDI note this oscillator responds to; notes below it are
+    /// silenced, same as `midi_note_high` for notes above. Lets a split
+    /// keyboard patch route low notes to a bass oscillator and high notes
+    /// to a lead oscillator within the same patch.
+    #[serde(default)]
+    pub midi_note_low: u8,
+    /// Highest MIDI note this oscillator responds to. Defaults to 127 (the
+    /// top of the MIDI range) via `default_midi_note_high`, so oscillators
+    /// saved before this field existed keep responding to every note.
+    #[serde(default = "default_midi_note_high")]
+    pub midi_note_high: u8,
+    /// Ensemble/chorus-style stereo widening via a modulated short delay.
+    /// `None` disables it and `Synth::get_stereo_sample` falls back to
+    /// plain `pan_split`.
+    pub stereo_delay: Option<StereoDelayMod>,
+    /// `stereo_delay`'s own LFO phase, advanced alongside `phase`.
+    stereo_delay_phase: f32,
+    /// `stereo_delay`'s delay line, holding up to `max_delay_ms` worth of
+    /// this oscillator's own samples.
+    #[serde(skip)]
+    stereo_delay_buffer: VecDeque<f32>,
+    /// Seeds `waveform`'s noise PRNG (`Waveform::WhiteNoise`/`PinkNoise`),
+    /// so the same seed always reproduces the same noise sequence — for
+    /// reproducible preview rendering and deterministic headless renders.
+    /// Randomly generated once in `Oscillator::new`; call `reseed_noise` to
+    /// pick a new one. Serializes with the oscillator like any other
+    /// preset field, so a saved patch keeps its noise character.
+    pub noise_seed: u64,
+}
+
+/// Per-oscillator ensemble/chorus effect: a short (1-30ms) delay whose
+/// length is swept by an LFO, read slightly ahead of center for the left
+/// channel and slightly behind for the right, producing natural stereo
+/// width without a separate effects-chain slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StereoDelayMod {
+    /// LFO sweep rate in Hz.
+    pub lfo_rate: f32,
+    /// Upper bound on the modulated delay, and the size of the delay line.
+    pub max_delay_ms: f32,
+    /// `0.0` keeps left and right reading the same modulated delay
+    /// (no width); `1.0` spreads them the full `max_delay_ms` apart.
+    pub stereo_spread: f32,
+}
+
+impl Default for StereoDelayMod {
+    fn default() -> Self {
+        Self { lfo_rate: 0.5, max_delay_ms: 15.0, stereo_spread: 0.5 }
+    }
+}
+
+impl Oscillator {
+    pub fn new(waveform: Waveform) -> Self {
+        let noise_seed = rand::random();
+        let mut waveform = waveform;
+        waveform.reset_noise_seed(noise_seed);
+        Self {
+            waveform,
+            phase: 0.0,
+            detune_semitones: 0.0,
+            pitch_shift_semitones: 0.0,
+            volume: 1.0,
+            enabled: true,
+            velocity_sensitivity: VelocitySensitivity::default(),
+            filter: Filter::default(),
+            filter_state: FilterState::for_type(FilterType::LowPass),
+            ring_mod_enabled: false,
+            ring_mod_mode: RingModMode::default(),
+            ring_mod_waveform: Waveform::Sine,
+            ring_mod_phase: 0.0,
+            pan: 0.0,
+            velocity_to_filter_cutoff: 0.0,
+            midi_note_low: 0,
+            midi_note_high: default_midi_note_high(),
+            stereo_delay: None,
+            stereo_delay_phase: 0.0,
+            stereo_delay_buffer: VecDeque::new(),
+            noise_seed,
+        }
+    }
+
+    /// Picks a new random `noise_seed` and immediately re-seeds `waveform`'s
+    /// noise PRNG from it, restarting the noise sequence from a fixed point.
+    pub fn reseed_noise(&mut self) {
+        self.noise_seed = rand::random();
+        self.waveform.reset_noise_seed(self.noise_seed);
+    }
+
+    /// Rebuilds `filter_state` to match `filter.filter_type` if it has
+    /// drifted out of sync (e.g. after the UI changes `filter.filter_type`
+    /// or a preset loads over this oscillator), resetting the filter's
+    /// memory to silence. A no-op when the two already agree, so calling
+    /// this every sample is cheap.
+    fn sync_filter_state(&mut self) {
+        let matches = matches!(
+            (&self.filter_state, self.filter.filter_type),
+            (FilterState::LowPass(_), FilterType::LowPass) | (FilterState::HighPass(_), FilterType::HighPass) | (FilterState::Bypass, FilterType::BandPass) | (FilterState::Bypass, FilterType::AllPass)
+        );
+        if !matches {
+            self.filter_state = FilterState::for_type(self.filter.filter_type);
+        }
+    }
+
+    /// Applies this oscillator's filter to `input`, resyncing `filter_state`
+    /// first in case `filter.filter_type` changed since the last sample.
+    pub fn apply_filter(&mut self, input: f32, sample_rate: f32) -> f32 {
+        self.sync_filter_state();
+        self.filter_state.process(input, self.filter.cutoff_hz, sample_rate)
+    }
+
+    /// Whether `note` falls within `midi_note_low..=midi_note_high`. Voices
+    /// triggered outside this oscillator's range still exist (so its
+    /// envelope/filter state stays consistent if the range changes mid-note)
+    /// but contribute silence; see `Synth::get_sample`/`get_stereo_sample`.
+    pub fn accepts_note(&self, note: u8) -> bool {
+        (self.midi_note_low..=self.midi_note_high).contains(&note)
+    }
+
+    /// Splits `value` into `(left, right)` using a constant-power pan law
+    /// so the perceived loudness stays constant as `pan` sweeps center.
+    pub fn pan_split(&self, value: f32) -> (f32, f32) {
+        let pan_angle = (self.pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+        (value * pan_angle.cos(), value * pan_angle.sin())
+    }
+
+    /// Splits `value` into `(left, right)` for `Synth::get_stereo_sample`,
+    /// via `stereo_delay`'s modulated delay line if set, falling back to
+    /// plain `pan_split` otherwise. When `stereo_delay` is active it takes
+    /// over stereo placement, since sweeping a pan position on top of a
+    /// modulated delay would fight the delay's own width.
+    pub fn stereo_delay_split(&mut self, value: f32, sample_rate: f32) -> (f32, f32) {
+        let Some(stereo_delay) = self.stereo_delay.clone() else {
+            return self.pan_split(value);
+        };
+
+        let max_delay_samples = ((stereo_delay.max_delay_ms * sample_rate / 1000.0).max(1.0)) as usize;
+        self.stereo_delay_buffer.push_back(value);
+        while self.stereo_delay_buffer.len() > max_delay_samples {
+            self.stereo_delay_buffer.pop_front();
+        }
+
+        self.stereo_delay_phase += stereo_delay.lfo_rate / sample_rate;
+        self.stereo_delay_phase -= self.stereo_delay_phase.floor();
+        let lfo = (self.stereo_delay_phase * std::f32::consts::TAU).sin();
+
+        let center_delay = max_delay_samples as f32 * 0.5;
+        let spread = stereo_delay.stereo_spread.clamp(0.0, 1.0) * center_delay;
+        let max_index = (max_delay_samples as f32 - 1.0).max(0.0);
+        let left_delay = (center_delay + lfo * spread).clamp(0.0, max_index);
+        let right_delay = (center_delay - lfo * spread).clamp(0.0, max_index);
+
+        (read_delay_interpolated(&self.stereo_delay_buffer, left_delay), read_delay_interpolated(&self.stereo_delay_buffer, right_delay))
+    }
+
+    /// Advances `phase` by one sample at `frequency` (after detune), and the
+    /// ring modulator's own phase alongside it, wrapping both into `0.0..1.0`.
+    pub fn advance_phase(&mut self, frequency: f32, sample_rate: f32) {
+        let detuned = frequency * 2.0f32.powf(self.detune_semitones / 12.0);
+        self.phase += detuned / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+        }
+
+        if self.ring_mod_enabled {
+            let ring_mod_freq = match self.ring_mod_mode {
+                RingModMode::Fixed(hz) => hz.clamp(0.1, 5000.0),
+                RingModMode::Ratio(ratio) => frequency * ratio,
+            };
+            self.ring_mod_phase += ring_mod_freq / sample_rate;
+            if self.ring_mod_phase >= 1.0 {
+                self.ring_mod_phase -= self.ring_mod_phase.floor();
+            }
+        }
+
+        if let Waveform::Supersaw(state) = &mut self.waveform {
+            state.advance(detuned, sample_rate);
+        }
+    }
+
+    /// The playback-rate multiplier `CustomWavetable::advance_position`
+    /// callers should fold into `sample_delta` so `pitch_shift_semitones`
+    /// takes effect: `1.0` at `0.0` semitones, doubling every octave.
+    pub fn sample_playback_rate_multiplier(&self) -> f32 {
+        2.0f32.powf(self.pitch_shift_semitones / 12.0)
+    }
+
+    pub fn sample(&mut self) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+        let mut value = self.waveform.sample(self.phase) * self.volume;
+        if self.ring_mod_enabled {
+            value *= self.ring_mod_waveform.sample(self.ring_mod_phase);
+        }
+        value
+    }
+}
+
+impl Default for Oscillator {
+    fn default() -> Self {
+        Self::new(Waveform::default())
+    }
+}
+
+/// Linearly interpolated read from `buffer`, `delay_samples` behind its
+/// most recently pushed sample. Used by `Oscillator::stereo_delay_split`.
+fn read_delay_interpolated(buffer: &VecDeque<f32>, delay_samples: f32) -> f32 {
+    if buffer.is_empty() {
+        return 0.0;
+    }
+    let read_position = (buffer.len() as f32 - 1.0 - delay_samples).max(0.0);
+    let index_low = read_position.floor() as usize;
+    let index_high = (index_low + 1).min(buffer.len() - 1);
+    let fraction = read_position - read_position.floor();
+    let sample_low = buffer[index_low];
+    let sample_high = buffer[index_high];
+    sample_low + (sample_high - sample_low) * fraction
+}
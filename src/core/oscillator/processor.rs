@@ -0,0 +1,20 @@
+use crate::core::oscillator::Oscillator;
+
+/// Advances `oscillator`'s phase by one sample at `frequency` and returns
+/// its output, pulled out of `Synth::get_sample`'s `Mix` branch so the
+/// per-oscillator step has one obvious place to grow into as more of the
+/// signal path (envelope, filter, LFO) moves from UI-only display values
+/// into the real per-sample path.
+///
+/// `Oscillator::filter`'s cutoff/resonance previously only drove
+/// `Filter::magnitude_response_db` for the UI's response-curve display and
+/// had no effect on audio. `Oscillator::apply_filter` now applies a real
+/// one-pole filter here for `LowPass`/`HighPass`; `BandPass`/`AllPass`
+/// pass through unfiltered. The displayed curve still differs from what is
+/// heard: it draws a 12 dB/octave slope and a resonance bump, while the
+/// one-pole stage rolls off at 6 dB/octave and ignores resonance.
+pub fn process_oscillator_sample(oscillator: &mut Oscillator, frequency: f32, sample_rate: f32) -> f32 {
+    oscillator.advance_phase(frequency, sample_rate);
+    let sample = oscillator.sample();
+    oscillator.apply_filter(sample, sample_rate)
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+
+/// A small, fast, non-cryptographic PRNG (xorshift64) used to make the
+/// noise waveforms reproducible: seeding two instances identically and
+/// drawing the same number of samples from each always yields the same
+/// sequence, unlike the global `rand::random()` calls this replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    /// `seed` of `0` would make xorshift stick at `0` forever, so it's
+    /// forced odd instead of rejected outright.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Next value in `-1.0..1.0`.
+    pub fn next_bipolar(&mut self) -> f32 {
+        let normalized = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        normalized * 2.0 - 1.0
+    }
+}
+
+impl Default for Xorshift64 {
+    fn default() -> Self {
+        Self::new(0x9E3779B97F4A7C15)
+    }
+}
+
+/// The shape of signal an `Oscillator` produces for a given phase.
+///
+/// `phase` is always normalized to `0.0..1.0` by the caller.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Square,
+    /// A square wave with a configurable duty cycle (`0.0..1.0`, `0.5` is
+    /// a standard square wave).
+    PulseWidth(f32),
+    Saw,
+    Triangle,
+    /// Holds its own seeded PRNG (rather than the global `rand::random()`)
+    /// so `Oscillator::noise_seed` can make it reproducible.
+    WhiteNoise(Xorshift64),
+    /// 1/f spectrum noise via the Voss-McCartney algorithm.
+    ///
+    /// Holds 16 independent white-noise generators updated on staggered
+    /// binary-counter intervals so consecutive calls sum to a pink
+    /// spectrum without any single generator dominating.
+    PinkNoise(PinkNoiseState),
+    /// Red noise produced by integrating white noise (a random walk clamped
+    /// to `-1.0..1.0`).
+    BrownNoise(BrownNoiseState),
+    /// A loaded multi-frame wavetable, identified by `index`. `Synth` holds
+    /// no wavetable data, so this variant renders as silence (see
+    /// `Waveform::sample`).
+    ///
+    /// `frame_position` (`0.0..1.0`) selects where to read within the
+    /// wavetable's frames, allowing the patch to morph through the table
+    /// instead of always reading frame 0.
+    CustomSample {
+        index: usize,
+        frame_position: f32,
+    },
+    /// Roland JP-8000-style composite of detuned saw waves, cheaper than
+    /// stacking unison voices since it needs no per-voice envelope.
+    Supersaw(SupersawState),
+    /// A smooth crossfade between two other waveforms, so a patch can sweep
+    /// between e.g. `Sine` and `Square` instead of switching abruptly.
+    /// `position` `0.0` is pure `from`, `1.0` is pure `to`.
+    Morph {
+        from: Box<Waveform>,
+        to: Box<Waveform>,
+        position: f32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PinkNoiseState {
+    generators: [f32; 16],
+    counter: u32,
+    rng: Xorshift64,
+}
+
+impl Default for PinkNoiseState {
+    fn default() -> Self {
+        Self {
+            generators: [0.0; 16],
+            counter: 0,
+            rng: Xorshift64::default(),
+        }
+    }
+}
+
+impl PinkNoiseState {
+    /// Advances the Voss-McCartney generator by one sample and returns the
+    /// next pink noise value, normalized to roughly `-1.0..1.0`.
+    pub fn next_sample(&mut self) -> f32 {
+        self.counter = self.counter.wrapping_add(1);
+        // Update only the generators whose bit flipped on this tick.
+        let changed = self.counter ^ self.counter.wrapping_sub(1);
+        for (i, gen) in self.generators.iter_mut().enumerate() {
+            if changed & (1 << i) != 0 {
+                *gen = self.rng.next_bipolar();
+            }
+        }
+        let sum: f32 = self.generators.iter().sum();
+        (sum / self.generators.len() as f32).clamp(-1.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrownNoiseState {
+    state: f32,
+    rng: Xorshift64,
+}
+
+impl Default for BrownNoiseState {
+    fn default() -> Self {
+        Self { state: 0.0, rng: Xorshift64::default() }
+    }
+}
+
+impl BrownNoiseState {
+    /// Adds a scaled fresh white noise sample to the running state, clamped
+    /// to `-1.0..1.0`.
+    pub fn next_sample(&mut self) -> f32 {
+        let random = self.rng.next_bipolar();
+        self.state = (self.state + random * 0.01).clamp(-1.0, 1.0);
+        self.state
+    }
+}
+
+/// Up to 7 detune offsets (in semitones at `spread = 1.0`) matching the
+/// JP-8000's non-uniform distribution: the center voice is untouched and
+/// the rest fan out asymmetrically rather than evenly.
+const SUPERSAW_DETUNE_SEMITONES: [f32; 7] = [
+    -0.11002313,
+    -0.06288439,
+    -0.01952356,
+    0.0,
+    0.01991221,
+    0.06216538,
+    0.10745242,
+];
+
+/// Per-note state for `Waveform::Supersaw`: each detuned saw keeps its own
+/// running phase rather than deriving it from the shared oscillator phase,
+/// since each voice advances at a different effective frequency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SupersawState {
+    /// Number of detuned saws to sum, 1-7.
+    pub voices: u8,
+    /// 0.0 (unison, no detune) to 1.0 (full JP-8000 spread).
+    pub spread: f32,
+    phases: Vec<f32>,
+}
+
+impl Default for SupersawState {
+    fn default() -> Self {
+        Self {
+            voices: 7,
+            spread: 0.5,
+            phases: vec![0.0; 7],
+        }
+    }
+}
+
+impl SupersawState {
+    /// Advances every active voice's phase at `base_frequency`, detuned per
+    /// `SUPERSAW_DETUNE_SEMITONES` and scaled by `spread`.
+    pub fn advance(&mut self, base_frequency: f32, sample_rate: f32) {
+        let voice_count = (self.voices as usize).clamp(1, SUPERSAW_DETUNE_SEMITONES.len());
+        if self.phases.len() != voice_count {
+            self.phases = vec![0.0; voice_count];
+        }
+        for (i, phase) in self.phases.iter_mut().enumerate() {
+            let semitones = SUPERSAW_DETUNE_SEMITONES[i] * self.spread;
+            let frequency = base_frequency * 2.0f32.powf(semitones / 12.0);
+            *phase += frequency / sample_rate;
+            if *phase >= 1.0 {
+                *phase -= phase.floor();
+            }
+        }
+    }
+
+    /// Sums the current saw value of every voice, normalized by voice count
+    /// so adding more voices doesn't raise the overall output level.
+    pub fn sample(&self) -> f32 {
+        if self.phases.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = self.phases.iter().map(|phase| 2.0 * phase - 1.0).sum();
+        sum / self.phases.len() as f32
+    }
+}
+
+impl Waveform {
+    /// Samples the waveform at `phase` (`0.0..1.0`).
+    ///
+    /// Stateful variants (`PinkNoise`, `BrownNoise`) take `&mut self` via
+    /// the caller holding a per-oscillator `Waveform` value, so noise state
+    /// is never shared across notes.
+    pub fn sample(&mut self, phase: f32) -> f32 {
+        match self {
+            Waveform::Sine => (phase * std::f32::consts::TAU).sin(),
+            Waveform::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::PulseWidth(duty_cycle) => {
+                if phase < duty_cycle.clamp(0.0, 1.0) {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Saw => 2.0 * phase - 1.0,
+            Waveform::Triangle => 4.0 * (phase - (phase + 0.25).floor()).abs() - 1.0,
+            Waveform::WhiteNoise(rng) => rng.next_bipolar(),
+            Waveform::PinkNoise(state) => state.next_sample(),
+            Waveform::BrownNoise(state) => state.next_sample(),
+            // No wavetable data reaches this path, and no `Synth` render
+            // path (`get_sample`, `fill_buffer`) reads a `CustomWavetable`
+            // for this variant either, so it always samples as silence.
+            Waveform::CustomSample { .. } => 0.0,
+            // Each voice tracks its own phase, advanced separately by
+            // `Oscillator::advance_phase`; the shared `phase` argument
+            // (derived from a single frequency) doesn't apply here.
+            Waveform::Supersaw(state) => state.sample(),
+            Waveform::Morph { from, to, position } => {
+                let from_val = from.sample(phase);
+                let to_val = to.sample(phase);
+                let position = position.clamp(0.0, 1.0);
+                from_val * (1.0 - position) + to_val * position
+            }
+        }
+    }
+
+    /// Re-seeds this waveform's noise PRNG (`WhiteNoise`/`PinkNoise`/
+    /// `BrownNoise`) from `seed`, restarting it at a fixed point so the same
+    /// seed always produces the same noise sequence from here on — used by
+    /// `Oscillator::reseed_noise` and to initialize a freshly constructed
+    /// oscillator. A no-op for every other variant.
+    pub fn reset_noise_seed(&mut self, seed: u64) {
+        match self {
+            Waveform::WhiteNoise(rng) => *rng = Xorshift64::new(seed),
+            Waveform::PinkNoise(state) => state.rng = Xorshift64::new(seed),
+            Waveform::BrownNoise(state) => state.rng = Xorshift64::new(seed),
+            Waveform::Morph { from, to, .. } => {
+                from.reset_noise_seed(seed);
+                to.reset_noise_seed(seed);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Parameter automation recorded against a `MidiFilePlayer`'s tick
+//! timeline, so filter sweeps, volume changes, and other parameter edits
+//! made during MIDI file recording play back alongside the notes.
+
+use serde::{Deserialize, Serialize};
+
+/// A synth parameter an `AutomationTrack` can drive. Oscillator-scoped
+/// variants carry the template index (0-2, matching `Synth::osc_peak_levels`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AutomationParameter {
+    OscillatorVolume(usize),
+    OscillatorPan(usize),
+    OscillatorFilterCutoff(usize),
+    A4TuningHz,
+}
+
+/// One recorded value at a tick position on an `AutomationTrack`'s timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutomationPoint {
+    pub tick: u64,
+    pub value: f32,
+}
+
+/// A timed sequence of values for a single `AutomationParameter`, recorded
+/// against a `MidiFilePlayer`'s tick position and replayed by
+/// `MidiFilePlayer::tick` alongside the file's own MIDI events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationTrack {
+    pub parameter: AutomationParameter,
+    pub points: Vec<AutomationPoint>,
+    /// Untoggling this keeps the recorded points around but stops them
+    /// from firing during playback or being overwritten while recording.
+    pub enabled: bool,
+}
+
+impl AutomationTrack {
+    pub fn new(parameter: AutomationParameter) -> Self {
+        Self {
+            parameter,
+            points: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    /// Records `value` at `tick`, overwriting any point already at that
+    /// exact tick, and keeping `points` in ascending tick order.
+    pub fn record(&mut self, tick: u64, value: f32) {
+        match self.points.iter_mut().find(|point| point.tick == tick) {
+            Some(existing) => existing.value = value,
+            None => {
+                self.points.push(AutomationPoint { tick, value });
+                self.points.sort_by_key(|point| point.tick);
+            }
+        }
+    }
+}
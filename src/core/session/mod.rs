@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::app::settings::AppSettings;
+use crate::core::sequencer::AutomationTrack;
+use crate::core::synth::preset::SynthPreset;
+
+/// The on-disk project format: everything needed to restore the app to
+/// where the user left it, not just the current patch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub version: u32,
+    pub preset: SynthPreset,
+    pub settings: AppSettings,
+    /// The MIDI file `automation_tracks` are recorded against, if one was
+    /// loaded into the sequencer.
+    #[serde(default)]
+    pub midi_file_path: Option<PathBuf>,
+    #[serde(default)]
+    pub automation_tracks: Vec<AutomationTrack>,
+}
+
+pub const CURRENT_SESSION_VERSION: u32 = 2;
+
+impl Session {
+    pub fn new(preset: SynthPreset, settings: AppSettings) -> Self {
+        Self {
+            version: CURRENT_SESSION_VERSION,
+            preset,
+            settings,
+            midi_file_path: None,
+            automation_tracks: Vec::new(),
+        }
+    }
+
+    pub fn save_to_file(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::write(path, json)
+    }
+
+    pub fn load_from_file(path: &std::path::Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
@@ -0,0 +1,308 @@
+//! Audio-thread-facing helpers for `Synth` sample generation.
+
+use super::{tuned_frequency, Synth};
+use std::sync::atomic::Ordering;
+
+/// How long a peak meter's atomic reading takes to decay back toward
+/// silence after the loudest sample in a block, per `fill_buffer` call.
+const PEAK_HOLD_SECS: f32 = 0.3;
+
+/// How `interpolate_sample` reconstructs a value between recorded samples
+/// when a `Waveform::CustomSample` is read at a non-integer position.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum InterpolationQuality {
+    /// Straight line between the two nearest samples. Cheapest, but
+    /// introduces audible aliasing at high playback pitches.
+    Linear,
+    /// 4-point Hermite cubic (Catmull-Rom), using the two samples on each
+    /// side of `position`.
+    #[default]
+    Cubic,
+    /// Windowed-sinc interpolation using `usize` samples on each side,
+    /// Hann-windowed to keep the kernel finite. Costliest, best fidelity.
+    Sinc(usize),
+}
+
+/// Reads `samples` at a fractional `position` using `quality`. `position`
+/// is clamped into the valid sample range; callers handle wraparound/looping
+/// themselves (see `CustomWavetable::advance_position`).
+pub fn interpolate_sample(samples: &[f32], position: f32, quality: &InterpolationQuality) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let last_index = samples.len() - 1;
+    let position = position.clamp(0.0, last_index as f32);
+    let base = position.floor() as usize;
+    let fraction = position - base as f32;
+
+    let at = |offset: isize| -> f32 {
+        let index = (base as isize + offset).clamp(0, last_index as isize) as usize;
+        samples[index]
+    };
+
+    match quality {
+        InterpolationQuality::Linear => at(0) * (1.0 - fraction) + at(1) * fraction,
+        InterpolationQuality::Cubic => {
+            let p0 = at(-1);
+            let p1 = at(0);
+            let p2 = at(1);
+            let p3 = at(2);
+            // Catmull-Rom cubic Hermite spline.
+            let a0 = -0.5 * p0 + 1.5 * p1 - 1.5 * p2 + 0.5 * p3;
+            let a1 = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+            let a2 = -0.5 * p0 + 0.5 * p2;
+            let a3 = p1;
+            ((a0 * fraction + a1) * fraction + a2) * fraction + a3
+        }
+        InterpolationQuality::Sinc(half_width) => {
+            let half_width = *half_width as isize;
+            let mut sum = 0.0;
+            for offset in -half_width..=half_width {
+                let x = fraction - offset as f32;
+                let sinc = if x.abs() < f32::EPSILON {
+                    1.0
+                } else {
+                    (std::f32::consts::PI * x).sin() / (std::f32::consts::PI * x)
+                };
+                // Hann window over the kernel's support, so the sinc tails
+                // taper to zero instead of ringing indefinitely.
+                let window = 0.5 * (1.0 + (std::f32::consts::PI * x / half_width as f32).cos());
+                sum += at(offset) * sinc * window;
+            }
+            sum
+        }
+    }
+}
+
+impl Synth {
+    /// Fills `output` (interleaved if `channels > 1`) with `output.len() /
+    /// channels` frames of synthesized audio in one call, batching voice
+    /// and oscillator processing across the whole block instead of the
+    /// per-sample `get_sample`/`get_stereo_sample` API. `channels == 1`
+    /// takes `fill_buffer`'s SIMD-accelerated mono fast path; any other
+    /// channel count falls back to `fill_buffer_multichannel`.
+    pub fn render_block(&mut self, output: &mut [f32], channels: usize) {
+        if channels <= 1 {
+            self.fill_buffer(output);
+            return;
+        }
+        self.fill_buffer_multichannel(output, channels);
+    }
+
+    /// Channels-aware fallback for `render_block` when `channels != 1`.
+    /// `EffectsChain::process` only ever handles a mono signal (matching
+    /// `get_sample`'s architecture), so the stereo pan mix is summed back
+    /// to mono before the effects chain, then broadcast to every channel
+    /// in the frame.
+    fn fill_buffer_multichannel(&mut self, output: &mut [f32], channels: usize) {
+        output.fill(0.0);
+        let sample_rate = self.sample_rate;
+        let frame_count = output.len() / channels;
+        for frame in 0..frame_count {
+            let (left, right) = self.get_stereo_sample();
+            let mut mix = self.effects_chain.process((left + right) * 0.5, sample_rate);
+            if mix.abs() > 1.0 {
+                self.master_clipped.store(true, Ordering::Relaxed);
+            }
+            if self.auto_limiter_enabled {
+                mix = mix.tanh();
+            }
+            let base = frame * channels;
+            for channel in output[base..base + channels].iter_mut() {
+                *channel += mix;
+            }
+        }
+    }
+
+    /// Fills `output` with one buffer's worth of mono samples, processing
+    /// all active voices per-block instead of per-sample. This is what
+    /// enables `generate_sine_block` to batch its SIMD work across a whole
+    /// callback instead of one sample at a time. Called directly for mono
+    /// output, or via `render_block` for a single-channel block.
+    pub fn fill_buffer(&mut self, output: &mut [f32]) {
+        output.fill(0.0);
+
+        let sample_rate = self.sample_rate;
+        // One bend multiplier per output sample, so a mid-block pitch-bend
+        // wheel movement still reaches the oscillators sample-accurately
+        // instead of only once per block like `frequency` below.
+        let pitch_bend = self.pitch_bend_block(output.len(), sample_rate);
+        let mut phases = Vec::with_capacity(self.voices.len());
+        let mut attack_ramp = Vec::with_capacity(output.len());
+        let mut osc_block_peaks = [0.0f32; 3];
+
+        for voice in self.voices.values_mut() {
+            let frequency = tuned_frequency(&self.tuning, self.transpose_semitones, self.fine_tune_semitones, voice.note);
+
+            // Per-sample attack envelope for this voice, computed once per
+            // block and reused across every oscillator below.
+            attack_ramp.clear();
+            let attack_step = 1.0 / (voice.attack_seconds * sample_rate);
+            let mut attack_gain = voice.attack_gain;
+            for _ in 0..output.len() {
+                attack_ramp.push(attack_gain);
+                attack_gain = (attack_gain + attack_step).min(1.0);
+            }
+            voice.attack_gain = attack_gain;
+
+            if let crate::core::oscillator::OscillatorCombinationMode::Additive(_) = &self.combination_mode {
+                for ((sample, attack), bend) in output.iter_mut().zip(attack_ramp.iter()).zip(pitch_bend.iter()) {
+                    voice.fundamental_phase += frequency * bend / sample_rate;
+                    voice.fundamental_phase -= voice.fundamental_phase.floor();
+                    *sample += self.combination_mode.sample_additive(voice.fundamental_phase) * attack;
+                }
+                continue;
+            }
+
+            for (index, (oscillator, gain)) in voice.oscillators.iter_mut().zip(voice.velocity_gains.iter()).enumerate() {
+                let range_gain = if oscillator.accepts_note(voice.note) { 1.0 } else { 0.0 };
+
+                if !matches!(oscillator.waveform, crate::core::oscillator::Waveform::Sine) {
+                    let mut peak = 0.0f32;
+                    for ((sample, attack), bend) in output.iter_mut().zip(attack_ramp.iter()).zip(pitch_bend.iter()) {
+                        oscillator.advance_phase(frequency * bend, sample_rate);
+                        let raw = oscillator.sample();
+                        let filtered = oscillator.apply_filter(raw, sample_rate);
+                        let value = filtered * gain * attack * range_gain;
+                        peak = peak.max(value.abs());
+                        *sample += value;
+                    }
+                    if let Some(slot) = osc_block_peaks.get_mut(index) {
+                        *slot = slot.max(peak);
+                    }
+                    continue;
+                }
+
+                phases.clear();
+                phases.resize(output.len(), 0.0);
+                let detuned = frequency * 2.0f32.powf(oscillator.detune_semitones / 12.0);
+                let mut phase = oscillator.phase;
+                for (slot, bend) in phases.iter_mut().zip(pitch_bend.iter()) {
+                    *slot = phase;
+                    phase += detuned * bend / sample_rate;
+                    if phase >= 1.0 {
+                        phase -= phase.floor();
+                    }
+                }
+                oscillator.phase = phase;
+
+                self.sine_block_scratch.clear();
+                self.sine_block_scratch.resize(output.len(), 0.0);
+                generate_sine_block(&phases, &mut self.sine_block_scratch);
+                let mut peak = 0.0f32;
+                for ((sample, value), attack) in output.iter_mut().zip(self.sine_block_scratch.iter()).zip(attack_ramp.iter()) {
+                    let filtered = oscillator.apply_filter(value * oscillator.volume, sample_rate);
+                    let contribution = filtered * gain * attack * range_gain;
+                    peak = peak.max(contribution.abs());
+                    *sample += contribution;
+                }
+                if let Some(slot) = osc_block_peaks.get_mut(index) {
+                    *slot = slot.max(peak);
+                }
+            }
+        }
+
+        for (sample, bend) in output.iter_mut().zip(pitch_bend.iter()) {
+            *sample += self.advance_releasing_voices_mono(*bend);
+            *sample += self.input_buffer.pop_front().unwrap_or(0.0) * self.input_mix;
+            *sample = self.effects_chain.process(*sample, sample_rate);
+        }
+
+        // Detect clipping against the raw mix, before any limiting, so the
+        // indicator reflects genuine overs rather than the limiter's
+        // already-tamed output.
+        self.update_peak_meters(output, &osc_block_peaks);
+        self.apply_auto_limiter(output);
+    }
+
+    /// Soft-clips `output` through `tanh` in place when `auto_limiter_enabled`
+    /// is set, otherwise leaves it untouched.
+    fn apply_auto_limiter(&self, output: &mut [f32]) {
+        if !self.auto_limiter_enabled {
+            return;
+        }
+        for sample in output.iter_mut() {
+            *sample = sample.tanh();
+        }
+    }
+
+    /// Decays each peak meter atomic by `decay_factor` (derived from
+    /// `PEAK_HOLD_SECS`) and raises it to this block's peak if louder, then
+    /// latches the corresponding clip flag if that peak exceeds 0 dBFS.
+    fn update_peak_meters(&self, output: &[f32], osc_block_peaks: &[f32; 3]) {
+        let decay_factor = (1.0 - 1.0 / (self.sample_rate * PEAK_HOLD_SECS)).powi(output.len() as i32);
+
+        for ((peak_level, clipped), block_peak) in self.osc_peak_levels.iter().zip(self.osc_clipped.iter()).zip(osc_block_peaks) {
+            let current = f32::from_bits(peak_level.load(Ordering::Relaxed));
+            let updated = block_peak.max(current * decay_factor);
+            peak_level.store(updated.to_bits(), Ordering::Relaxed);
+            if *block_peak > 1.0 {
+                clipped.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let master_block_peak = output.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        let current_master = f32::from_bits(self.master_peak_level.load(Ordering::Relaxed));
+        let updated_master = master_block_peak.max(current_master * decay_factor);
+        self.master_peak_level.store(updated_master.to_bits(), Ordering::Relaxed);
+        if master_block_peak > 1.0 {
+            self.master_clipped.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Computes `sin(phase * TAU)` for four phases at a time using SSE2
+/// intrinsics with a 5th-order minimax polynomial approximation. Falls
+/// back to the scalar `f32::sin` on non-x86_64 targets.
+#[cfg(target_arch = "x86_64")]
+pub fn generate_sine_block(phases: &[f32], output: &mut [f32]) {
+    use std::arch::x86_64::*;
+
+    const TAU: f32 = std::f32::consts::TAU;
+    // Minimax coefficients for sin(x) on [-pi, pi], odd-power terms only.
+    const C1: f32 = 0.984_413_55;
+    const C3: f32 = -0.153_461_37;
+    const C5: f32 = 0.005_465_276;
+
+    // SSE2 has no `_mm_floor_ps` (that's SSE4.1); truncate toward zero and
+    // correct for negative inputs that truncated upward.
+    #[target_feature(enable = "sse2")]
+    unsafe fn sse2_floor(v: __m128) -> __m128 {
+        let truncated = _mm_cvtepi32_ps(_mm_cvttps_epi32(v));
+        let too_high = _mm_cmpgt_ps(truncated, v);
+        _mm_sub_ps(truncated, _mm_and_ps(too_high, _mm_set1_ps(1.0)))
+    }
+
+    let len = phases.len();
+    let mut i = 0;
+    unsafe {
+        while i + 4 <= len {
+            let p = _mm_loadu_ps(phases[i..].as_ptr());
+            // Wrap phase*TAU into [-pi, pi] by centering around 0.5 turns.
+            let half = _mm_set1_ps(0.5);
+            let wrapped = _mm_sub_ps(p, sse2_floor(_mm_add_ps(p, half)));
+            let x = _mm_mul_ps(wrapped, _mm_set1_ps(TAU));
+
+            let x2 = _mm_mul_ps(x, x);
+            let c1 = _mm_set1_ps(C1);
+            let c3 = _mm_set1_ps(C3);
+            let c5 = _mm_set1_ps(C5);
+            let poly = _mm_add_ps(c1, _mm_mul_ps(x2, _mm_add_ps(c3, _mm_mul_ps(x2, c5))));
+            let result = _mm_mul_ps(x, poly);
+
+            _mm_storeu_ps(output[i..].as_mut_ptr(), result);
+            i += 4;
+        }
+    }
+    // Scalar tail for lengths not divisible by 4.
+    for j in i..len {
+        output[j] = (phases[j] * TAU).sin();
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn generate_sine_block(phases: &[f32], output: &mut [f32]) {
+    for (phase, sample) in phases.iter().zip(output.iter_mut()) {
+        *sample = (phase * std::f32::consts::TAU).sin();
+    }
+}
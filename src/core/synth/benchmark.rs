@@ -0,0 +1,108 @@
+//! Headless throughput benchmark for `Synth::render_block`, driving the
+//! CLI's `--benchmark` flag and `SynthApp::run_benchmark`. Measures how many
+//! samples per second this machine can synthesize under a few representative
+//! patch configurations, with no real audio output in the loop.
+
+use std::time::{Duration, Instant};
+
+use crate::core::oscillator::{HarmonicPartial, Oscillator, OscillatorCombinationMode, Waveform};
+use crate::core::synth::Synth;
+
+/// How long each configuration renders for before its throughput is
+/// measured.
+const BENCHMARK_DURATION: Duration = Duration::from_secs(5);
+const SAMPLE_RATE: f32 = 44_100.0;
+/// Block size passed to `render_block` each iteration, matching a common
+/// real-world cpal buffer size (see `audio_settings`'s buffer size options).
+const BLOCK_SIZE: usize = 512;
+
+/// One configuration's measured throughput.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    pub label: String,
+    pub samples_per_sec: f64,
+    /// `samples_per_sec / SAMPLE_RATE`: how many times faster than real-time
+    /// this configuration rendered. Below `1.0` means this hardware can't
+    /// keep up with `SAMPLE_RATE` played live.
+    pub real_time_factor: f64,
+}
+
+/// Runs every benchmark configuration: a single oscillator, three
+/// oscillators under each combination mode, and 16 simultaneous notes with
+/// all three oscillators active. Each renders `BLOCK_SIZE`-frame blocks in a
+/// tight loop for `BENCHMARK_DURATION` real time.
+pub fn run() -> Vec<BenchmarkResult> {
+    configs()
+        .into_iter()
+        .map(|(label, mut synth)| {
+            let mut block = vec![0.0f32; BLOCK_SIZE];
+            let start = Instant::now();
+            let mut total_samples: u64 = 0;
+            while start.elapsed() < BENCHMARK_DURATION {
+                synth.render_block(&mut block, 1);
+                total_samples += BLOCK_SIZE as u64;
+            }
+            let elapsed_secs = start.elapsed().as_secs_f64().max(f64::EPSILON);
+            let samples_per_sec = total_samples as f64 / elapsed_secs;
+            BenchmarkResult {
+                label: label.to_string(),
+                samples_per_sec,
+                real_time_factor: samples_per_sec / SAMPLE_RATE as f64,
+            }
+        })
+        .collect()
+}
+
+/// Formats `results` as the `| Config | Samples/sec | Real-time factor |`
+/// table printed by `--benchmark` and shown by the "Run Benchmark" button.
+pub fn format_table(results: &[BenchmarkResult]) -> String {
+    let mut table = String::from("| Config | Samples/sec | Real-time factor |\n|---|---|---|\n");
+    for result in results {
+        table.push_str(&format!("| {} | {:.0} | {:.2}x |\n", result.label, result.samples_per_sec, result.real_time_factor));
+    }
+    table
+}
+
+fn configs() -> Vec<(&'static str, Synth)> {
+    vec![
+        ("1 oscillator", single_oscillator_synth()),
+        ("3 oscillators (Mix)", three_oscillator_synth(OscillatorCombinationMode::Mix)),
+        ("3 oscillators (Additive)", three_oscillator_synth(additive_mode())),
+        ("16 notes, all oscillators", sixteen_note_synth()),
+    ]
+}
+
+fn single_oscillator_synth() -> Synth {
+    let mut synth = Synth::new(SAMPLE_RATE);
+    synth.oscillator_templates = vec![Oscillator::new(Waveform::Saw)];
+    synth.note_on(60, 100);
+    synth
+}
+
+fn three_oscillator_synth(combination_mode: OscillatorCombinationMode) -> Synth {
+    let mut synth = Synth::new(SAMPLE_RATE);
+    synth.oscillator_templates = vec![
+        Oscillator::new(Waveform::Saw),
+        Oscillator::new(Waveform::Square),
+        Oscillator::new(Waveform::Sine),
+    ];
+    synth.combination_mode = combination_mode;
+    synth.note_on(60, 100);
+    synth
+}
+
+fn additive_mode() -> OscillatorCombinationMode {
+    OscillatorCombinationMode::Additive(vec![
+        HarmonicPartial { harmonic_number: 1, amplitude: 1.0 },
+        HarmonicPartial { harmonic_number: 2, amplitude: 0.5 },
+        HarmonicPartial { harmonic_number: 3, amplitude: 0.25 },
+    ])
+}
+
+fn sixteen_note_synth() -> Synth {
+    let mut synth = three_oscillator_synth(OscillatorCombinationMode::Mix);
+    for note in 48..48 + 16 {
+        synth.note_on(note, 100);
+    }
+    synth
+}
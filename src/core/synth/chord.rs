@@ -0,0 +1,112 @@
+/// Automatically harmonizes a single played note into a chord. Each variant
+/// other than `Disabled` carries the semitone offsets (above the root) of
+/// the notes to add alongside it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ChordMode {
+    #[default]
+    Disabled,
+    Major,
+    Minor,
+    Dom7,
+    Maj7,
+    Min7,
+    Sus2,
+    Sus4,
+    Custom(Vec<i8>),
+}
+
+impl ChordMode {
+    /// Semitone offsets above the root to sound alongside it. Empty for
+    /// `Disabled`, so the root plays alone.
+    pub fn intervals(&self) -> &[i8] {
+        match self {
+            ChordMode::Disabled => &[],
+            ChordMode::Major => &[4, 7],
+            ChordMode::Minor => &[3, 7],
+            ChordMode::Dom7 => &[4, 7, 10],
+            ChordMode::Maj7 => &[4, 7, 11],
+            ChordMode::Min7 => &[3, 7, 10],
+            ChordMode::Sus2 => &[2, 7],
+            ChordMode::Sus4 => &[5, 7],
+            ChordMode::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// How `chord_mode`'s notes are spread across the pitch range, applied to
+/// the root plus intervals before they're dispatched as individual
+/// `note_on` calls.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ChordVoicing {
+    /// All notes as close to the root as possible (the raw interval
+    /// offsets, unchanged).
+    #[default]
+    Closed,
+    /// Spread across two octaves: alternating notes are raised an octave,
+    /// widening the chord instead of stacking it close.
+    Open,
+    /// The second-highest note of a closed voicing is dropped an octave,
+    /// a common jazz/arranging voicing that thins out the top of the chord.
+    Drop2,
+    /// Chooses, independently for each note above the root, whichever
+    /// octave keeps it closest to the same note in the previous chord
+    /// (`Synth::previous_chord_notes`), minimizing total pitch movement.
+    Optimal,
+}
+
+impl ChordVoicing {
+    /// Applies this voicing to `notes` (root first, then each interval
+    /// note, all already clamped into `0..=127`), returning the re-voiced
+    /// note list in the same order. `previous_notes` is the prior chord's
+    /// notes, used only by `Optimal`.
+    pub fn apply(&self, notes: &[u8], previous_notes: &[u8]) -> Vec<u8> {
+        match self {
+            ChordVoicing::Closed => notes.to_vec(),
+            ChordVoicing::Open => notes
+                .iter()
+                .enumerate()
+                .map(|(index, &note)| {
+                    if index > 0 && index % 2 == 1 {
+                        (note as i16 + 12).clamp(0, 127) as u8
+                    } else {
+                        note
+                    }
+                })
+                .collect(),
+            ChordVoicing::Drop2 => {
+                let mut voiced = notes.to_vec();
+                if voiced.len() >= 2 {
+                    let mut sorted_indices: Vec<usize> = (0..voiced.len()).collect();
+                    sorted_indices.sort_by_key(|&i| voiced[i]);
+                    let second_highest = sorted_indices[sorted_indices.len() - 2];
+                    voiced[second_highest] = (voiced[second_highest] as i16 - 12).clamp(0, 127) as u8;
+                }
+                voiced
+            }
+            ChordVoicing::Optimal => notes
+                .iter()
+                .enumerate()
+                .map(|(index, &note)| if index == 0 { note } else { closest_octave_to_previous(note, previous_notes) })
+                .collect(),
+        }
+    }
+}
+
+/// Picks whichever octave of `note` (its pitch class shifted by whole
+/// octaves) lies closest to any note in `previous_notes`, for smooth voice
+/// leading. Falls back to `note` unchanged if there's no previous chord.
+fn closest_octave_to_previous(note: u8, previous_notes: &[u8]) -> u8 {
+    if previous_notes.is_empty() {
+        return note;
+    }
+    (0..127)
+        .filter(|candidate| candidate % 12 == note % 12)
+        .min_by_key(|&candidate| {
+            previous_notes
+                .iter()
+                .map(|&previous| (candidate as i16 - previous as i16).abs())
+                .min()
+                .unwrap_or(i16::MAX)
+        })
+        .unwrap_or(note)
+}
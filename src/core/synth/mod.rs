@@ -0,0 +1,765 @@
+pub mod audio;
+pub mod benchmark;
+pub mod chord;
+pub mod phase_table;
+pub mod preset;
+pub mod retrigger;
+pub mod samples;
+pub mod sf2;
+pub mod velocity;
+
+use crate::core::effects::EffectsChain;
+use crate::core::macros::{default_macros, MacroKnob, ModTarget};
+use crate::core::oscillator::{process_oscillator_sample, Oscillator, OscillatorCombinationMode, OscillatorPhaseReset};
+use crate::core::theory::Scale;
+use crate::core::tuning::Tuning;
+use crate::core::voice::{self, NotePriority};
+pub use chord::{ChordMode, ChordVoicing};
+pub use phase_table::VoicePhaseTable;
+pub use retrigger::RetriggerMode;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::sync::Arc;
+pub use velocity::{VelocityLaw, VelocityScalingConfig};
+
+/// Base attack time (at velocity 127, before `VelocityScalingConfig`
+/// stretches it) for a freshly triggered voice's `attack_gain` ramp.
+pub const ATTACK_SECONDS: f32 = 0.005;
+
+/// Semitones of pitch bend at full wheel deflection (`pitch_bend` of
+/// +/-1.0), the common MIDI default.
+pub const PITCH_BEND_RANGE_SEMITONES: f32 = 2.0;
+
+/// Milliseconds `pitch_bend`'s audible effect takes to catch up to a newly
+/// set value, so a wheel jump reaches the new pitch as a short ramp instead
+/// of an instant (and audibly "zipper") frequency jump.
+pub const PITCH_BEND_SLEW_MS: f32 = 5.0;
+
+/// A currently-sounding note and the per-oscillator phase state it owns.
+#[derive(Debug, Clone)]
+pub struct Voice {
+    pub note: u8,
+    pub velocity: u8,
+    pub oscillators: Vec<Oscillator>,
+    pub active: bool,
+    /// Per-oscillator velocity gain, computed once at `note_on` from each
+    /// oscillator's `velocity_sensitivity` so it isn't recomputed per sample.
+    pub velocity_gains: Vec<f32>,
+    /// The fundamental's running phase, advanced once per sample and used
+    /// by `OscillatorCombinationMode::Additive` to derive harmonic partials.
+    pub fundamental_phase: f32,
+    /// Monotonic order this voice was triggered in, used by
+    /// `NotePriority::LastPlayed` voice stealing.
+    pub played_at: u64,
+    /// Shared by every note sounded from the same `chord_mode` trigger, so
+    /// releasing any one of them (via `note_off`) releases the whole chord.
+    /// `None` for notes played individually.
+    pub chord_group_id: Option<u64>,
+    /// Linear gain multiplier, ramped from 1.0 to 0.0 over this voice's
+    /// `release_seconds` once it is displaced by a
+    /// `RetriggerMode::NoteOff` retrigger. Always 1.0 otherwise.
+    pub release_gain: f32,
+    /// Linear gain multiplier, ramped from 0.0 to 1.0 over this voice's
+    /// attack time, so the note fades in instead of starting instantly at
+    /// full level. Reaches 1.0 and stays there once the attack completes.
+    pub attack_gain: f32,
+    /// This voice's attack time in seconds, computed once at trigger time
+    /// from `ATTACK_SECONDS` and `Synth::velocity_scaling`.
+    pub attack_seconds: f32,
+    /// This voice's release time in seconds (used once it's displaced into
+    /// `releasing_voices`), computed once at trigger time from
+    /// `retrigger::RELEASE_SECONDS` and `Synth::velocity_scaling`.
+    pub release_seconds: f32,
+}
+
+/// The polyphonic synthesis engine. Owns one set of oscillator templates
+/// plus one `Voice` per currently-sounding note.
+pub struct Synth {
+    pub oscillator_templates: Vec<Oscillator>,
+    pub voices: HashMap<u8, Voice>,
+    pub sample_rate: f32,
+    pub combination_mode: OscillatorCombinationMode,
+    pub max_polyphony: Option<usize>,
+    pub note_priority: NotePriority,
+    pub tuning: Tuning,
+    /// Whole semitones applied to every note before tuning lookup.
+    pub transpose_semitones: i8,
+    /// Fractional semitones of fine-tune, applied on top of transpose.
+    pub fine_tune_semitones: f32,
+    /// Normalized pitch-bend wheel position, -1.0 to 1.0, synced every
+    /// callback from `SynthParameters::pitch_bend`. Every voice's frequency
+    /// follows this, smoothed by `pitch_bend_current_semitones` so it never
+    /// jumps instantly.
+    pub pitch_bend: f32,
+    /// `pitch_bend * PITCH_BEND_RANGE_SEMITONES`, slewed toward over
+    /// `PITCH_BEND_SLEW_MS` by `step_pitch_bend`/`pitch_bend_block` rather
+    /// than applied immediately.
+    pitch_bend_current_semitones: f32,
+    /// When set to anything but `Disabled`, `note_on` harmonizes the played
+    /// note with the chord shape's intervals.
+    pub chord_mode: ChordMode,
+    /// How `chord_mode`'s notes are spread across the pitch range.
+    pub chord_voicing: ChordVoicing,
+    /// The most recently sounded chord's (post-voicing) note numbers, used
+    /// by `ChordVoicing::Optimal` to minimize pitch movement between
+    /// successive chords.
+    previous_chord_notes: Vec<u8>,
+    /// Scratch buffer for `audio::fill_buffer`'s `generate_sine_block` call,
+    /// resized (never freshly allocated) by every sine oscillator in every
+    /// block so the audio callback doesn't hit the allocator.
+    sine_block_scratch: Vec<f32>,
+    /// Reconstruction quality for `Waveform::CustomSample` playback.
+    pub interpolation_quality: audio::InterpolationQuality,
+    /// How raw MIDI velocity is mapped to amplitude before each
+    /// oscillator's own `VelocitySensitivity` curve is applied.
+    pub velocity_law: VelocityLaw,
+    /// How note velocity stretches each voice's attack/release time, on top
+    /// of `velocity_law`'s amplitude curve.
+    pub velocity_scaling: VelocityScalingConfig,
+    /// Post-voice-mix effects, processed in order in `get_sample`.
+    pub effects_chain: EffectsChain,
+    /// Per-oscillator-template (index 0-2) peak level, as `f32::to_bits`,
+    /// updated by `fill_buffer` on every callback. Read by the UI's
+    /// per-oscillator meters via `SynthApp`.
+    pub osc_peak_levels: [Arc<AtomicU32>; 3],
+    /// Latched true once an oscillator's peak exceeds 0 dBFS; stays set
+    /// until the UI explicitly clears it by clicking the clip indicator.
+    pub osc_clipped: [Arc<AtomicBool>; 3],
+    /// Peak level of the final buffer mix, as `f32::to_bits`.
+    pub master_peak_level: Arc<AtomicU32>,
+    /// Latched true once the master mix exceeds 0 dBFS.
+    pub master_clipped: Arc<AtomicBool>,
+    /// How `trigger_voice` handles retriggering an already-sounding note.
+    pub retrigger_mode: RetriggerMode,
+    /// When set, `note_on` snaps incoming notes to the nearest tone in
+    /// this scale before sounding them.
+    pub scale_quantize: Option<Scale>,
+    /// Voices displaced by a `RetriggerMode::NoteOff` retrigger, fading out
+    /// via `release_gain` until pruned.
+    releasing_voices: Vec<Voice>,
+    next_play_order: u64,
+    next_chord_group_id: u64,
+    /// Captured external-input samples, pushed by the audio thread's input
+    /// stream callback and drained one-per-sample by `get_sample`/
+    /// `fill_buffer` so a guitar, mic, or other line-in signal can be
+    /// processed through the synth's effects chain.
+    pub input_buffer: VecDeque<f32>,
+    /// Linear gain applied to `input_buffer` samples before they're mixed
+    /// into the voice mix. `0.0` mutes captured input entirely.
+    pub input_mix: f32,
+    /// Held-but-not-released MIDI note numbers in press order, used by every
+    /// `note_priority` whose `NotePriority::retargets_held_notes` is true
+    /// (`Highest`, `Lowest`, `LastNoteMonophonic`), so releasing a note
+    /// retargets to the held note `NotePriority::retarget_from` picks,
+    /// rather than going silent. Unused for `LastPlayed`.
+    held_notes_stack: Vec<u8>,
+    /// Master-level fallback for whether a new voice's oscillators restart
+    /// from phase `0.0`.
+    pub phase_reset_mode: OscillatorPhaseReset,
+    /// When true, each new voice's oscillators additionally get a random
+    /// phase offset (the same offset across all of one voice's oscillators)
+    /// on top of `phase_reset_mode`, to break up the constructive
+    /// interference that otherwise stacks when many notes start together.
+    pub voice_phase_offset: bool,
+    voice_phase_table: VoicePhaseTable,
+    /// Whether the sustain pedal (MIDI CC64) is currently held down.
+    sustain_pedal: bool,
+    /// Notes released by `note_off` while `sustain_pedal` is down, kept
+    /// sounding until the pedal lifts.
+    sustained_notes: Vec<u8>,
+    /// When true, `fill_buffer`/`fill_buffer_multichannel` soft-clip the
+    /// final mix through `tanh` to tame overs instead of letting them clip
+    /// outright in the output stream.
+    pub auto_limiter_enabled: bool,
+    /// Eight performance macro knobs, each fanning its `0.0..1.0` value out
+    /// to whichever parameters its `routes` list. Applied on top of
+    /// `oscillator_templates`/`effects_chain` by `reapply_macros`, which
+    /// runs after every parameter snapshot lands so a macro's routes always
+    /// win over the raw (un-modulated) patch values they override.
+    pub macros: [MacroKnob; 8],
+}
+
+impl Synth {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            oscillator_templates: vec![Oscillator::default()],
+            voices: HashMap::new(),
+            sample_rate,
+            combination_mode: OscillatorCombinationMode::default(),
+            max_polyphony: None,
+            note_priority: NotePriority::default(),
+            tuning: Tuning::default(),
+            transpose_semitones: 0,
+            fine_tune_semitones: 0.0,
+            pitch_bend: 0.0,
+            pitch_bend_current_semitones: 0.0,
+            chord_mode: ChordMode::default(),
+            chord_voicing: ChordVoicing::default(),
+            previous_chord_notes: Vec::new(),
+            sine_block_scratch: Vec::new(),
+            interpolation_quality: audio::InterpolationQuality::default(),
+            velocity_law: VelocityLaw::default(),
+            velocity_scaling: VelocityScalingConfig::default(),
+            effects_chain: EffectsChain::default(),
+            osc_peak_levels: std::array::from_fn(|_| Arc::new(AtomicU32::new(0.0f32.to_bits()))),
+            osc_clipped: std::array::from_fn(|_| Arc::new(AtomicBool::new(false))),
+            master_peak_level: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            master_clipped: Arc::new(AtomicBool::new(false)),
+            retrigger_mode: RetriggerMode::default(),
+            scale_quantize: None,
+            releasing_voices: Vec::new(),
+            next_play_order: 0,
+            next_chord_group_id: 0,
+            input_buffer: VecDeque::new(),
+            input_mix: 0.0,
+            held_notes_stack: Vec::new(),
+            phase_reset_mode: OscillatorPhaseReset::default(),
+            voice_phase_offset: false,
+            voice_phase_table: VoicePhaseTable::default(),
+            sustain_pedal: false,
+            sustained_notes: Vec::new(),
+            auto_limiter_enabled: false,
+            macros: default_macros(),
+        }
+    }
+
+    /// Sets macro `index`'s value (clamped to `0.0..1.0`) and immediately
+    /// re-applies every macro's routes. No-op if `index` is out of range.
+    pub fn set_macro(&mut self, index: usize, value: f32) {
+        if let Some(knob) = self.macros.get_mut(index) {
+            knob.value = value.clamp(0.0, 1.0);
+        }
+        self.reapply_macros();
+    }
+
+    /// Applies every macro's routes to their targets, in knob order. A
+    /// parameter targeted by more than one route (whether on the same or a
+    /// different macro) simply ends up at whichever route applied last —
+    /// there is no summing or priority system, the same "last write wins"
+    /// behavior a user moving two overlapping MIDI-learned controls would
+    /// see.
+    pub fn reapply_macros(&mut self) {
+        for knob in self.macros.clone() {
+            for route in &knob.routes {
+                let mapped = route.mapped_value(knob.value);
+                match route.target {
+                    ModTarget::OscillatorVolume(i) => {
+                        if let Some(oscillator) = self.oscillator_templates.get_mut(i) {
+                            oscillator.volume = mapped;
+                        }
+                    }
+                    ModTarget::OscillatorPan(i) => {
+                        if let Some(oscillator) = self.oscillator_templates.get_mut(i) {
+                            oscillator.pan = mapped;
+                        }
+                    }
+                    ModTarget::OscillatorDetune(i) => {
+                        if let Some(oscillator) = self.oscillator_templates.get_mut(i) {
+                            oscillator.detune_semitones = mapped;
+                        }
+                    }
+                    ModTarget::FilterCutoff(i) => {
+                        if let Some(oscillator) = self.oscillator_templates.get_mut(i) {
+                            oscillator.filter.cutoff_hz = mapped;
+                        }
+                    }
+                    ModTarget::FilterResonance(i) => {
+                        if let Some(oscillator) = self.oscillator_templates.get_mut(i) {
+                            oscillator.filter.resonance = mapped;
+                        }
+                    }
+                    ModTarget::EffectWetDry(i) => {
+                        if let Some(slot) = self.effects_chain.slots.get_mut(i) {
+                            slot.wet_dry = mapped;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn note_on(&mut self, note: u8, velocity: u8) {
+        let note = match &self.scale_quantize {
+            Some(scale) => scale.quantize_note(note),
+            None => note,
+        };
+        if self.note_priority.retargets_held_notes() {
+            self.held_notes_stack.retain(|&held| held != note);
+            self.held_notes_stack.push(note);
+        }
+        let intervals = self.chord_mode.intervals();
+        if intervals.is_empty() {
+            self.trigger_voice(note, velocity, None);
+            return;
+        }
+
+        let raw_notes: Vec<u8> = std::iter::once(note)
+            .chain(intervals.iter().map(|offset| (note as i16 + *offset as i16).clamp(0, 127) as u8))
+            .collect();
+        let voiced_notes = self.chord_voicing.apply(&raw_notes, &self.previous_chord_notes);
+        self.previous_chord_notes = voiced_notes.clone();
+
+        let group_id = self.next_chord_group_id;
+        self.next_chord_group_id += 1;
+        for voiced_note in voiced_notes {
+            self.trigger_voice(voiced_note, velocity, Some(group_id));
+        }
+    }
+
+    /// Inserts a single voice, stealing one per `max_polyphony`/`note_priority`
+    /// if the synth is already at its polyphony limit.
+    fn trigger_voice(&mut self, note: u8, velocity: u8, chord_group_id: Option<u64>) {
+        if let Some(max_polyphony) = self.max_polyphony {
+            if self.voices.len() >= max_polyphony && !self.voices.contains_key(&note) {
+                let active_notes: Vec<(u8, u64)> = self
+                    .voices
+                    .values()
+                    .map(|voice| (voice.note, voice.played_at))
+                    .collect();
+                if let Some(stolen) = voice::choose_note_to_steal(&active_notes, self.note_priority) {
+                    self.voices.remove(&stolen);
+                }
+            }
+        }
+
+        let played_at = self.next_play_order;
+        self.next_play_order += 1;
+        let mut oscillators = self.oscillator_templates.clone();
+        let mut fundamental_phase = 0.0;
+        let mut phase_carried_over = false;
+
+        // `Gate` mode reattaches legato-style: carry over the displaced
+        // voice's phases instead of restarting them from zero.
+        if self.retrigger_mode == RetriggerMode::Gate {
+            if let Some(previous) = self.voices.get(&note) {
+                for (oscillator, previous_oscillator) in oscillators.iter_mut().zip(previous.oscillators.iter()) {
+                    oscillator.phase = previous_oscillator.phase;
+                }
+                fundamental_phase = previous.fundamental_phase;
+                phase_carried_over = true;
+            }
+        }
+
+        if !phase_carried_over {
+            // `NeverReset`: pick up wherever an already-sounding voice's
+            // oscillators are, instead of starting every voice in lockstep
+            // at phase 0.0 (the source of the constructive-interference
+            // aliasing peaks this mode exists to avoid).
+            if self.phase_reset_mode == OscillatorPhaseReset::NeverReset {
+                if let Some(reference) = self.voices.values().next() {
+                    for (oscillator, reference_oscillator) in oscillators.iter_mut().zip(reference.oscillators.iter()) {
+                        oscillator.phase = reference_oscillator.phase;
+                    }
+                }
+            }
+            if self.voice_phase_offset {
+                // Same offset for every oscillator in this voice, so
+                // oscillators that rely on a fixed relative phase (e.g.
+                // FM/ring-mod carrier-modulator pairs) stay coherent.
+                let offset = self.voice_phase_table.offset_for(played_at);
+                for oscillator in oscillators.iter_mut() {
+                    oscillator.phase = (oscillator.phase + offset).fract();
+                }
+            }
+        }
+
+        let velocity_amplitude = velocity::compute_velocity_amplitude(velocity, &self.velocity_law);
+        let velocity_gains = oscillators
+            .iter()
+            .map(|oscillator| oscillator.velocity_sensitivity.gain(velocity_amplitude))
+            .collect();
+        for oscillator in oscillators.iter_mut() {
+            if oscillator.velocity_to_filter_cutoff != 0.0 {
+                let octaves = oscillator.velocity_to_filter_cutoff * velocity_amplitude;
+                oscillator.filter.cutoff_hz = (oscillator.filter.cutoff_hz * 2.0f32.powf(octaves)).clamp(20.0, 20_000.0);
+            }
+        }
+        let attack_seconds = ATTACK_SECONDS * self.velocity_scaling.attack_scale_for(velocity);
+        let release_seconds = retrigger::RELEASE_SECONDS * self.velocity_scaling.release_scale_for(velocity);
+        let new_voice = Voice {
+            note,
+            velocity,
+            oscillators,
+            active: true,
+            velocity_gains,
+            fundamental_phase,
+            played_at,
+            chord_group_id,
+            release_gain: 1.0,
+            attack_gain: 0.0,
+            attack_seconds,
+            release_seconds,
+        };
+
+        let displaced = self.voices.insert(note, new_voice);
+        // `NoteOff` mode sends the displaced voice into release instead of
+        // dropping it, so its tail overlaps the new voice's attack.
+        if self.retrigger_mode == RetriggerMode::NoteOff {
+            if let Some(mut displaced) = displaced {
+                displaced.active = false;
+                self.releasing_voices.push(displaced);
+            }
+        }
+    }
+
+    pub fn note_off(&mut self, note: u8) {
+        if self.sustain_pedal {
+            if self.voices.contains_key(&note) {
+                self.sustained_notes.retain(|&held| held != note);
+                self.sustained_notes.push(note);
+            }
+            return;
+        }
+
+        let released_velocity = self.voices.get(&note).map(|voice| voice.velocity);
+        let chord_group_id = self.voices.get(&note).and_then(|voice| voice.chord_group_id);
+        match chord_group_id {
+            Some(group_id) => {
+                self.voices.retain(|_, voice| voice.chord_group_id != Some(group_id));
+            }
+            None => {
+                self.voices.remove(&note);
+            }
+        }
+
+        if self.note_priority.retargets_held_notes() {
+            self.held_notes_stack.retain(|&held| held != note);
+            if let (Some(retarget_note), Some(velocity)) = (self.note_priority.retarget_from(&self.held_notes_stack), released_velocity) {
+                self.trigger_voice(retarget_note, velocity, None);
+            }
+        }
+    }
+
+    /// Sets the sustain pedal (MIDI CC64) state. Lifting it (`down = false`)
+    /// releases every note that was held only because `note_off` arrived
+    /// while the pedal was down.
+    pub fn set_sustain_pedal(&mut self, down: bool) {
+        self.sustain_pedal = down;
+        if !down {
+            for note in std::mem::take(&mut self.sustained_notes) {
+                self.note_off(note);
+            }
+        }
+    }
+
+    /// MIDI panic: immediately silences every active voice. Unlike
+    /// releasing each note individually, this drops voices outright rather
+    /// than honoring any release stage, for use when a stuck note or
+    /// runaway MIDI feed needs to be silenced right away.
+    pub fn all_notes_off(&mut self) {
+        self.voices.clear();
+        self.sustained_notes.clear();
+    }
+
+    /// Hard reset for recovering from a stuck or misbehaving state (stuck
+    /// notes, a runaway effect), distinct from `all_notes_off`: in addition
+    /// to dropping every voice, it clears the note-tracking state those
+    /// voices leave behind and rebuilds every effect slot from scratch,
+    /// discarding whatever delay lines, envelopes, or filter history it had
+    /// accumulated. Every parameter (volume, envelope, waveform, effect
+    /// settings) is left untouched — only transient per-note and per-effect
+    /// state resets.
+    pub fn panic_reset(&mut self) {
+        self.voices.clear();
+        self.releasing_voices.clear();
+        self.sustained_notes.clear();
+        self.held_notes_stack.clear();
+        self.sustain_pedal = false;
+        self.previous_chord_notes.clear();
+
+        for slot in self.effects_chain.slots.iter_mut() {
+            slot.effect = slot.effect.effect_type().create();
+        }
+    }
+
+    /// Sums one sample across all active voices.
+    pub fn get_sample(&mut self) -> f32 {
+        let sample_rate = self.sample_rate;
+        let bend = self.step_pitch_bend(sample_rate);
+        let mut mix = 0.0;
+        for voice in self.voices.values_mut() {
+            let frequency = tuned_frequency(&self.tuning, self.transpose_semitones, self.fine_tune_semitones, voice.note) * bend;
+            match &self.combination_mode {
+                OscillatorCombinationMode::Mix => {
+                    for (oscillator, gain) in voice.oscillators.iter_mut().zip(voice.velocity_gains.iter()) {
+                        let range_gain = if oscillator.accepts_note(voice.note) { 1.0 } else { 0.0 };
+                        mix += process_oscillator_sample(oscillator, frequency, sample_rate) * gain * voice.attack_gain * range_gain;
+                    }
+                }
+                OscillatorCombinationMode::Additive(_) => {
+                    voice.fundamental_phase += frequency / sample_rate;
+                    voice.fundamental_phase -= voice.fundamental_phase.floor();
+                    mix += self.combination_mode.sample_additive(voice.fundamental_phase) * voice.attack_gain;
+                }
+            }
+            voice.attack_gain = (voice.attack_gain + 1.0 / (voice.attack_seconds * sample_rate)).min(1.0);
+        }
+        mix += self.advance_releasing_voices_mono(bend);
+        mix += self.input_buffer.pop_front().unwrap_or(0.0) * self.input_mix;
+        self.effects_chain.process(mix, sample_rate)
+    }
+
+    /// Spreads the first three oscillator templates across the stereo
+    /// field (-0.4, 0.0, +0.4), a common one-click width preset for
+    /// `OscillatorCombinationMode::Mix` patches.
+    pub fn apply_pan_spread(&mut self) {
+        const SPREAD: [f32; 3] = [-0.4, 0.0, 0.4];
+        for (oscillator, pan) in self.oscillator_templates.iter_mut().zip(SPREAD) {
+            oscillator.pan = pan;
+        }
+    }
+
+    /// Sums one stereo sample across all active voices, panning each
+    /// oscillator with a constant-power law via `Oscillator::pan_split`.
+    /// `OscillatorCombinationMode::Additive` has no per-partial pan, so its
+    /// contribution is summed to the center.
+    pub fn get_stereo_sample(&mut self) -> (f32, f32) {
+        let sample_rate = self.sample_rate;
+        let bend = self.step_pitch_bend(sample_rate);
+        let mut left = 0.0;
+        let mut right = 0.0;
+        for voice in self.voices.values_mut() {
+            let frequency = tuned_frequency(&self.tuning, self.transpose_semitones, self.fine_tune_semitones, voice.note) * bend;
+            match &self.combination_mode {
+                OscillatorCombinationMode::Mix => {
+                    for (oscillator, gain) in voice.oscillators.iter_mut().zip(voice.velocity_gains.iter()) {
+                        let range_gain = if oscillator.accepts_note(voice.note) { 1.0 } else { 0.0 };
+                        let sample = process_oscillator_sample(oscillator, frequency, sample_rate) * gain * voice.attack_gain * range_gain;
+                        let (panned_left, panned_right) = oscillator.stereo_delay_split(sample, sample_rate);
+                        left += panned_left;
+                        right += panned_right;
+                    }
+                }
+                OscillatorCombinationMode::Additive(_) => {
+                    voice.fundamental_phase += frequency / sample_rate;
+                    voice.fundamental_phase -= voice.fundamental_phase.floor();
+                    let value = self.combination_mode.sample_additive(voice.fundamental_phase) * voice.attack_gain;
+                    left += value;
+                    right += value;
+                }
+            }
+            voice.attack_gain = (voice.attack_gain + 1.0 / (voice.attack_seconds * sample_rate)).min(1.0);
+        }
+        let (releasing_left, releasing_right) = self.advance_releasing_voices_stereo(bend);
+        let input = self.input_buffer.pop_front().unwrap_or(0.0) * self.input_mix;
+        (left + releasing_left + input, right + releasing_right + input)
+    }
+
+    /// Mixes and decays `releasing_voices` by one sample, returning their
+    /// summed mono contribution, and prunes any that have fully faded.
+    /// Shared by `get_sample` and `audio::fill_buffer`.
+    fn advance_releasing_voices_mono(&mut self, bend: f32) -> f32 {
+        let sample_rate = self.sample_rate;
+        let mut releasing_voices = std::mem::take(&mut self.releasing_voices);
+        let mut mix = 0.0;
+        for voice in releasing_voices.iter_mut() {
+            let frequency = self.note_frequency(voice.note) * bend;
+            match &self.combination_mode {
+                OscillatorCombinationMode::Mix => {
+                    for (oscillator, gain) in voice.oscillators.iter_mut().zip(voice.velocity_gains.iter()) {
+                        oscillator.advance_phase(frequency, sample_rate);
+                        mix += oscillator.sample() * gain * voice.release_gain;
+                    }
+                }
+                OscillatorCombinationMode::Additive(_) => {
+                    voice.fundamental_phase += frequency / sample_rate;
+                    voice.fundamental_phase -= voice.fundamental_phase.floor();
+                    mix += self.combination_mode.sample_additive(voice.fundamental_phase) * voice.release_gain;
+                }
+            }
+            voice.release_gain -= 1.0 / (voice.release_seconds * sample_rate);
+        }
+        releasing_voices.retain(|voice| voice.release_gain > 0.0);
+        self.releasing_voices = releasing_voices;
+        mix
+    }
+
+    /// Stereo counterpart to `advance_releasing_voices_mono`, panning each
+    /// releasing oscillator the same way `get_stereo_sample` does.
+    fn advance_releasing_voices_stereo(&mut self, bend: f32) -> (f32, f32) {
+        let sample_rate = self.sample_rate;
+        let mut releasing_voices = std::mem::take(&mut self.releasing_voices);
+        let mut left = 0.0;
+        let mut right = 0.0;
+        for voice in releasing_voices.iter_mut() {
+            let frequency = self.note_frequency(voice.note) * bend;
+            match &self.combination_mode {
+                OscillatorCombinationMode::Mix => {
+                    for (oscillator, gain) in voice.oscillators.iter_mut().zip(voice.velocity_gains.iter()) {
+                        oscillator.advance_phase(frequency, sample_rate);
+                        let value = oscillator.sample() * gain * voice.release_gain;
+                        let (panned_left, panned_right) = oscillator.pan_split(value);
+                        left += panned_left;
+                        right += panned_right;
+                    }
+                }
+                OscillatorCombinationMode::Additive(_) => {
+                    voice.fundamental_phase += frequency / sample_rate;
+                    voice.fundamental_phase -= voice.fundamental_phase.floor();
+                    let value = self.combination_mode.sample_additive(voice.fundamental_phase) * voice.release_gain;
+                    left += value;
+                    right += value;
+                }
+            }
+            voice.release_gain -= 1.0 / (voice.release_seconds * sample_rate);
+        }
+        releasing_voices.retain(|voice| voice.release_gain > 0.0);
+        self.releasing_voices = releasing_voices;
+        (left, right)
+    }
+
+    /// Retunes the reference pitch (MIDI note 69, ordinarily "A4") used by
+    /// `tuning`, for historical (415 Hz) or orchestral (442-443 Hz)
+    /// standards other than the default 440 Hz. Clamped to 400-480 Hz.
+    pub fn set_a4_tuning_hz(&mut self, hz: f32) {
+        self.tuning.reference_frequency_hz = hz.clamp(400.0, 480.0);
+    }
+
+    /// Frequency for `note` after applying global transpose and fine-tune
+    /// on top of the active `Tuning`. Pitch bend is deliberately not
+    /// folded in here: it moves within a single sample (or block), while
+    /// this is evaluated once per voice, so callers multiply it in
+    /// separately via `step_pitch_bend`/`pitch_bend_block`.
+    fn note_frequency(&self, note: u8) -> f32 {
+        tuned_frequency(&self.tuning, self.transpose_semitones, self.fine_tune_semitones, note)
+    }
+
+    /// Advances `pitch_bend_current_semitones` by one sample toward
+    /// `pitch_bend * PITCH_BEND_RANGE_SEMITONES` and returns the
+    /// corresponding frequency multiplier. Shared by every voice in a
+    /// sample so they all bend in lockstep; call exactly once per sample.
+    fn step_pitch_bend(&mut self, sample_rate: f32) -> f32 {
+        let target = self.pitch_bend * PITCH_BEND_RANGE_SEMITONES;
+        // Linear ramp sized to cross the full -range..=+range span in
+        // `PITCH_BEND_SLEW_MS`, the same fixed-rate-ramp approach
+        // `attack_gain`/`release_gain` use elsewhere in this file.
+        let max_step = (2.0 * PITCH_BEND_RANGE_SEMITONES) / (PITCH_BEND_SLEW_MS / 1000.0 * sample_rate);
+        let diff = target - self.pitch_bend_current_semitones;
+        self.pitch_bend_current_semitones = if diff.abs() <= max_step {
+            target
+        } else {
+            self.pitch_bend_current_semitones + max_step * diff.signum()
+        };
+        self.pitch_bend_multiplier()
+    }
+
+    /// Frequency multiplier for the bend amount `step_pitch_bend` has
+    /// already slewed to this sample, without advancing it further. Used
+    /// by the releasing-voices helpers, which run after the main voice
+    /// loop has already stepped the bend for this sample.
+    fn pitch_bend_multiplier(&self) -> f32 {
+        2.0f32.powf(self.pitch_bend_current_semitones / 12.0)
+    }
+
+    /// `step_pitch_bend`, called once per sample for `count` samples in a
+    /// row, for `audio::fill_buffer`'s block-based path where voices can't
+    /// re-read a per-sample multiplier as cheaply as `get_sample` does.
+    pub(crate) fn pitch_bend_block(&mut self, count: usize, sample_rate: f32) -> Vec<f32> {
+        (0..count).map(|_| self.step_pitch_bend(sample_rate)).collect()
+    }
+}
+
+/// Frequency for `note` after applying `transpose_semitones`/
+/// `fine_tune_semitones` on top of `tuning`, split out of
+/// `Synth::note_frequency` as a free function so callers already holding a
+/// mutable borrow of `Synth::voices` (e.g. per-voice mixing loops) can still
+/// reach it without borrowing `self` as a whole.
+pub(crate) fn tuned_frequency(tuning: &Tuning, transpose_semitones: i8, fine_tune_semitones: f32, note: u8) -> f32 {
+    let transposed = (note as i32 + transpose_semitones as i32).clamp(0, 127) as u8;
+    tuning.frequency_for_note(transposed) * 2.0f32.powf(fine_tune_semitones / 12.0)
+}
+
+/// Converts a MIDI note number to frequency in Hz using A4 = 440 Hz.
+pub fn midi_note_to_frequency(note: u8) -> f32 {
+    440.0 * 2.0f32.powf((note as f32 - 69.0) / 12.0)
+}
+
+/// Converts a frequency in Hz to the nearest MIDI note number plus the
+/// remaining deviation in cents (negative means flat, positive means sharp).
+pub fn frequency_to_midi_note(frequency_hz: f32) -> (u8, f32) {
+    let note_float = 69.0 + 12.0 * (frequency_hz / 440.0).log2();
+    let note = note_float.round().clamp(0.0, 127.0);
+    let cents = (note_float - note) * 100.0;
+    (note as u8, cents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const C4: u8 = 60;
+    const E4: u8 = 64;
+    const G4: u8 = 67;
+
+    /// Holding C, E, G in sequence and releasing G should retarget to E,
+    /// the most recently pressed still-held note, not go silent.
+    #[test]
+    fn last_note_monophonic_retargets_to_previous_held_note() {
+        let mut synth = Synth::new(44_100.0);
+        synth.note_priority = NotePriority::LastNoteMonophonic;
+        synth.max_polyphony = Some(1);
+
+        synth.note_on(C4, 100);
+        synth.note_on(E4, 100);
+        synth.note_on(G4, 100);
+        synth.note_off(G4);
+
+        assert!(!synth.voices.contains_key(&G4));
+        assert!(synth.voices.contains_key(&E4));
+
+        synth.note_off(E4);
+        assert!(!synth.voices.contains_key(&E4));
+        assert!(synth.voices.contains_key(&C4));
+
+        synth.note_off(C4);
+        assert!(synth.voices.is_empty());
+    }
+
+    /// Under `Lowest` priority, releasing the currently sounding note
+    /// retargets to the lowest remaining held note.
+    #[test]
+    fn lowest_priority_retargets_to_lowest_held_note() {
+        let mut synth = Synth::new(44_100.0);
+        synth.note_priority = NotePriority::Lowest;
+        synth.max_polyphony = Some(1);
+
+        synth.note_on(C4, 100);
+        synth.note_on(E4, 100);
+        synth.note_on(G4, 100);
+        synth.note_off(G4);
+
+        assert!(synth.voices.contains_key(&C4));
+    }
+
+    /// Under `Highest` priority, releasing the currently sounding note
+    /// retargets to the highest remaining held note.
+    #[test]
+    fn highest_priority_retargets_to_highest_held_note() {
+        let mut synth = Synth::new(44_100.0);
+        synth.note_priority = NotePriority::Highest;
+        synth.max_polyphony = Some(1);
+
+        synth.note_on(C4, 100);
+        synth.note_on(E4, 100);
+        synth.note_on(G4, 100);
+        synth.note_off(G4);
+
+        assert!(synth.voices.contains_key(&E4));
+    }
+
+    /// `choose_note_to_steal` and `NotePriority::retarget_from` must agree on
+    /// which note survives: the one `choose_note_to_steal` leaves unstolen
+    /// should be the same one `retarget_from` would pick to keep sounding.
+    #[test]
+    fn stealing_and_retargeting_agree_on_which_note_survives() {
+        let active_notes = [(C4, 0), (E4, 1), (G4, 2)];
+        let held_notes = [C4, E4, G4];
+
+        for priority in [NotePriority::Highest, NotePriority::Lowest] {
+            let stolen = voice::choose_note_to_steal(&active_notes, priority).unwrap();
+            let survivors: Vec<u8> = held_notes.iter().copied().filter(|&note| note != stolen).collect();
+            let retargeted = priority.retarget_from(&held_notes).unwrap();
+            assert!(survivors.contains(&retargeted), "{priority:?} stole {stolen} but would retarget to {retargeted}");
+        }
+    }
+}
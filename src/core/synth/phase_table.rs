@@ -0,0 +1,36 @@
+use rand::{Rng, SeedableRng};
+use rand_xorshift::XorShiftRng;
+
+/// Number of precomputed offsets. Large enough that voices don't audibly
+/// repeat a phase pattern in normal play; indexed with wraparound so it
+/// never runs out.
+const TABLE_SIZE: usize = 64;
+
+/// Precomputed random phase offsets, so enabling `Synth::voice_phase_offset`
+/// produces deterministic behavior (the same seed always yields the same
+/// sequence of offsets) instead of calling `rand::random` fresh per voice.
+#[derive(Debug, Clone)]
+pub struct VoicePhaseTable {
+    offsets: Vec<f32>,
+}
+
+impl VoicePhaseTable {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+        let offsets = (0..TABLE_SIZE).map(|_| rng.gen::<f32>()).collect();
+        Self { offsets }
+    }
+
+    /// Returns the offset for voice allocation order `played_at`, to be
+    /// applied identically to every oscillator in that voice so their
+    /// phase relationships (e.g. for FM or ring modulation) stay coherent.
+    pub fn offset_for(&self, played_at: u64) -> f32 {
+        self.offsets[(played_at as usize) % self.offsets.len()]
+    }
+}
+
+impl Default for VoicePhaseTable {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
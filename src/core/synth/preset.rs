@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::macros::{default_macros, MacroKnob};
+use crate::core::oscillator::{FilterType, Oscillator, Waveform};
+use crate::core::synth::Synth;
+use rand::{Rng, SeedableRng};
+use rand_xorshift::XorShiftRng;
+
+/// Fixed note, velocity, and length used to audition a preset in
+/// `generate_preview`, so every preset's preview is directly comparable.
+const PREVIEW_NOTE: u8 = 60; // C4
+const PREVIEW_VELOCITY: u8 = 100;
+const PREVIEW_DURATION_SECONDS: f32 = 2.0;
+const PREVIEW_RELEASE_AT_SECONDS: f32 = 1.2;
+
+/// The current on-disk preset schema version. Bump this whenever a field
+/// is added, removed, or reinterpreted, and add a migration arm in
+/// `SynthPreset::migrate`.
+pub const CURRENT_PRESET_VERSION: u32 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynthPreset {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    pub name: String,
+    pub oscillator_templates: Vec<Oscillator>,
+    #[serde(default = "default_author")]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// RFC 3339 timestamp string rather than a `chrono`/`SystemTime` value,
+    /// so presets stay plain JSON and human-readable on disk.
+    #[serde(default)]
+    pub created_at: String,
+    /// This preset's eight performance macro knobs, including their routes.
+    #[serde(default = "default_macros")]
+    pub macros: [MacroKnob; 8],
+}
+
+fn default_version() -> u32 {
+    // Presets saved before the `version` field existed are schema v1.
+    1
+}
+
+fn default_author() -> String {
+    "Unknown".to_string()
+}
+
+impl SynthPreset {
+    pub fn new(name: String, oscillator_templates: Vec<Oscillator>, author: String, description: String, tags: Vec<String>, created_at: String) -> Self {
+        Self {
+            version: CURRENT_PRESET_VERSION,
+            name,
+            oscillator_templates,
+            author,
+            description,
+            tags,
+            created_at,
+            macros: default_macros(),
+        }
+    }
+
+    /// Loads and migrates a preset from JSON, bringing it up to
+    /// `CURRENT_PRESET_VERSION` before handing it to serde.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        Self::migrate(value)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Applies schema migrations to raw JSON, in sequence, until `version`
+    /// reaches `CURRENT_PRESET_VERSION`, then deserializes the result.
+    /// Operating on a `Value` rather than the typed `SynthPreset` matters
+    /// the moment a migration needs to rename or restructure a field:
+    /// `#[serde(default)]` can fill in a field that's merely missing, but
+    /// can't rescue a deserialize that fails outright because an old field
+    /// name is gone. A preset with no `version` field at all (from before
+    /// versioning existed) is treated as version 1, matching `SynthPreset`'s
+    /// own `default_version`. Each arm only needs to know how to step from
+    /// its version to the next one.
+    pub fn migrate(mut value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        loop {
+            let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+            if version >= CURRENT_PRESET_VERSION {
+                break;
+            }
+            match version {
+                1 => {
+                    // v1 -> v2: no structural change yet, just stamp the
+                    // version now that `version` is a tracked field.
+                }
+                2 => {
+                    // v2 -> v3: author/description/tags/created_at were
+                    // added additively; `serde(default)` fills them in on
+                    // the typed deserialize below, so nothing to rewrite
+                    // here yet.
+                }
+                3 => {
+                    // v3 -> v4: macros were added additively;
+                    // `serde(default = "default_macros")` fills it in on
+                    // the typed deserialize below, so nothing to rewrite
+                    // here yet.
+                }
+                _ => break,
+            }
+            value["version"] = serde_json::Value::from(version + 1);
+        }
+        serde_json::from_value(value)
+    }
+
+    /// Returns a copy of this preset with small, reproducible random
+    /// tweaks applied to each oscillator. `mutation_rate` (`0.0..=1.0`)
+    /// sets both how likely each continuous parameter is to change and how
+    /// far it moves; discrete parameters (waveform, filter type) only
+    /// change with probability `mutation_rate * 0.3`, since flipping them
+    /// is a much bigger jump than nudging a number.
+    pub fn mutate(&self, mutation_rate: f32, seed: u64) -> SynthPreset {
+        let mutation_rate = mutation_rate.clamp(0.0, 1.0);
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+        let mut mutated = self.clone();
+
+        for oscillator in &mut mutated.oscillator_templates {
+            mutate_continuous(&mut oscillator.volume, mutation_rate, 0.0, 1.0, &mut rng);
+            mutate_continuous(&mut oscillator.pan, mutation_rate, -1.0, 1.0, &mut rng);
+            mutate_continuous(&mut oscillator.detune_semitones, mutation_rate, -24.0, 24.0, &mut rng);
+            mutate_continuous(&mut oscillator.filter.cutoff_hz, mutation_rate, 20.0, 20_000.0, &mut rng);
+            mutate_continuous(&mut oscillator.filter.resonance, mutation_rate, 0.0, 1.0, &mut rng);
+
+            if rng.gen::<f32>() < mutation_rate * 0.3 {
+                oscillator.waveform = random_basic_waveform(&mut rng);
+            }
+            if rng.gen::<f32>() < mutation_rate * 0.3 {
+                oscillator.filter.filter_type = random_filter_type(&mut rng);
+            }
+        }
+
+        mutated
+    }
+
+    /// Renders a short audition clip of this preset on a scratch `Synth`:
+    /// `PREVIEW_NOTE` held from `0.0` through `PREVIEW_RELEASE_AT_SECONDS`,
+    /// then released for the remainder of `PREVIEW_DURATION_SECONDS`. Used
+    /// by the preset browser to generate a cacheable preview waveform
+    /// without disturbing the real, currently-playing `Synth`.
+    pub fn generate_preview(&self, sample_rate: f32) -> Vec<f32> {
+        let mut synth = Synth::new(sample_rate);
+        synth.oscillator_templates = self.oscillator_templates.clone();
+        synth.note_on(PREVIEW_NOTE, PREVIEW_VELOCITY);
+
+        let total_frames = (PREVIEW_DURATION_SECONDS * sample_rate).round() as usize;
+        let release_at_frame = (PREVIEW_RELEASE_AT_SECONDS * sample_rate).round() as usize;
+        let mut samples = Vec::with_capacity(total_frames);
+        for frame in 0..total_frames {
+            if frame == release_at_frame {
+                synth.note_off(PREVIEW_NOTE);
+            }
+            samples.push(synth.get_sample());
+        }
+        samples
+    }
+
+    /// Compares this preset against `other`, field by field: name, author,
+    /// description and tags, plus each oscillator's `enabled` flag and the
+    /// per-oscillator parameters `mutate` can change.
+    /// Oscillators are compared by index; if one preset has more
+    /// oscillators than the other, the extras are reported as added or
+    /// removed rather than compared field-by-field.
+    pub fn diff(&self, other: &SynthPreset) -> PresetDiff {
+        let mut entries = Vec::new();
+
+        diff_field(&mut entries, "name", &self.name, &other.name);
+        diff_field(&mut entries, "author", &self.author, &other.author);
+        diff_field(&mut entries, "description", &self.description, &other.description);
+        diff_field(&mut entries, "tags", &format!("{:?}", self.tags), &format!("{:?}", other.tags));
+
+        let oscillator_count = self.oscillator_templates.len().min(other.oscillator_templates.len());
+        for index in 0..oscillator_count {
+            diff_oscillator(&mut entries, index, &self.oscillator_templates[index], &other.oscillator_templates[index]);
+        }
+        for index in oscillator_count..self.oscillator_templates.len() {
+            entries.push(PresetDiffEntry {
+                field: format!("oscillator[{index}]"),
+                before: "present".to_string(),
+                after: "removed".to_string(),
+            });
+        }
+        for index in oscillator_count..other.oscillator_templates.len() {
+            entries.push(PresetDiffEntry {
+                field: format!("oscillator[{index}]"),
+                before: "absent".to_string(),
+                after: "added".to_string(),
+            });
+        }
+
+        PresetDiff { entries }
+    }
+}
+
+/// One changed field between two presets, identified by name (e.g.
+/// `"name"` or `"oscillator[0].volume"`) alongside its old and new value
+/// already formatted for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetDiffEntry {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// The set of fields that differ between two presets, as returned by
+/// `SynthPreset::diff`. Empty `entries` means the presets are equivalent
+/// in every field compared.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PresetDiff {
+    pub entries: Vec<PresetDiffEntry>,
+}
+
+impl PresetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn diff_field(entries: &mut Vec<PresetDiffEntry>, field: &str, before: &str, after: &str) {
+    if before != after {
+        entries.push(PresetDiffEntry { field: field.to_string(), before: before.to_string(), after: after.to_string() });
+    }
+}
+
+/// Diffs the `mutate`-eligible fields of one oscillator pair, plus
+/// `enabled`, prefixing
+/// each entry's field name with `oscillator[index]`.
+fn diff_oscillator(entries: &mut Vec<PresetDiffEntry>, index: usize, before: &Oscillator, after: &Oscillator) {
+    let prefix = format!("oscillator[{index}]");
+    diff_field(entries, &format!("{prefix}.waveform"), &format!("{:?}", before.waveform), &format!("{:?}", after.waveform));
+    diff_field(entries, &format!("{prefix}.enabled"), &before.enabled.to_string(), &after.enabled.to_string());
+    diff_field(entries, &format!("{prefix}.volume"), &before.volume.to_string(), &after.volume.to_string());
+    diff_field(entries, &format!("{prefix}.pan"), &before.pan.to_string(), &after.pan.to_string());
+    diff_field(entries, &format!("{prefix}.detune_semitones"), &before.detune_semitones.to_string(), &after.detune_semitones.to_string());
+    diff_field(
+        entries,
+        &format!("{prefix}.filter.filter_type"),
+        &format!("{:?}", before.filter.filter_type),
+        &format!("{:?}", after.filter.filter_type),
+    );
+    diff_field(entries, &format!("{prefix}.filter.cutoff_hz"), &before.filter.cutoff_hz.to_string(), &after.filter.cutoff_hz.to_string());
+    diff_field(entries, &format!("{prefix}.filter.resonance"), &before.filter.resonance.to_string(), &after.filter.resonance.to_string());
+}
+
+/// Nudges `value` within `[min, max]` by a random offset scaled by
+/// `mutation_rate`, with probability `mutation_rate` of changing at all.
+fn mutate_continuous(value: &mut f32, mutation_rate: f32, min: f32, max: f32, rng: &mut XorShiftRng) {
+    if rng.gen::<f32>() >= mutation_rate {
+        return;
+    }
+    let range = max - min;
+    let offset = (rng.gen::<f32>() * 2.0 - 1.0) * range * mutation_rate;
+    *value = (*value + offset).clamp(min, max);
+}
+
+fn random_basic_waveform(rng: &mut XorShiftRng) -> Waveform {
+    match rng.gen_range(0..4) {
+        0 => Waveform::Sine,
+        1 => Waveform::Square,
+        2 => Waveform::Saw,
+        _ => Waveform::Triangle,
+    }
+}
+
+fn random_filter_type(rng: &mut XorShiftRng) -> FilterType {
+    match rng.gen_range(0..3) {
+        0 => FilterType::LowPass,
+        1 => FilterType::HighPass,
+        _ => FilterType::BandPass,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_0_preset_loads_cleanly_with_defaults() {
+        let json = r#"{
+            "name": "Pre-Versioning Patch",
+            "oscillator_templates": []
+        }"#;
+
+        let preset = SynthPreset::from_json(json).expect("version-0 preset should migrate and deserialize");
+
+        assert_eq!(preset.version, CURRENT_PRESET_VERSION);
+        assert_eq!(preset.name, "Pre-Versioning Patch");
+        assert_eq!(preset.author, "Unknown");
+        assert!(preset.description.is_empty());
+        assert!(preset.tags.is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+/// How `Synth::trigger_voice` handles retriggering a note that's already
+/// sounding, e.g. pressing a held key (or chord) again before releasing it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RetriggerMode {
+    /// Silently replaces the old voice with the new one. Can click if the
+    /// old voice was still loud.
+    #[default]
+    Reset,
+    /// Lets the old voice fade out over `RELEASE_SECONDS` while the new
+    /// voice starts fresh, so the release tail overlaps the new attack
+    /// instead of cutting off abruptly.
+    NoteOff,
+    /// Reuses the old voice's oscillator phases for the new voice instead
+    /// of resetting them, for legato-style reattacks.
+    Gate,
+}
+
+/// How long a `RetriggerMode::NoteOff` retrigger's displaced voice takes to
+/// fade to silence.
+pub const RELEASE_SECONDS: f32 = 0.05;
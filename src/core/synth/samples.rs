@@ -0,0 +1,337 @@
+use std::path::{Path, PathBuf};
+
+/// Whether a `CustomWavetable` plays through once and stops, or loops.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PlaybackMode {
+    #[default]
+    OneShot,
+    Looping,
+}
+
+/// A user-loaded sample used by `Waveform::CustomSample`. Holds one or more
+/// frames (for wavetable morphing) of mono `f32` audio.
+#[derive(Debug, Clone, Default)]
+pub struct CustomWavetable {
+    pub name: String,
+    pub sample_rate: u32,
+    pub frames: Vec<Vec<f32>>,
+    /// Fraction (`0.0..1.0`) into each frame where playback should begin.
+    pub start_position: f32,
+    pub playback_mode: PlaybackMode,
+}
+
+impl CustomWavetable {
+    /// Advances `position` (a frame-relative index) by `sample_delta`
+    /// samples, honoring `start_position` and `playback_mode`. Returns
+    /// `None` once a one-shot has finished playing. To pitch-shift playback
+    /// independently of the triggering note, callers should scale
+    /// `sample_delta` by the playing `Oscillator`'s
+    /// `sample_playback_rate_multiplier()` before passing it in.
+    pub fn advance_position(&self, frame: &[f32], position: f32, sample_delta: f32) -> Option<f32> {
+        let start = self.start_position.clamp(0.0, 1.0) * frame.len() as f32;
+        let mut next = position + sample_delta;
+        if next >= frame.len() as f32 {
+            match self.playback_mode {
+                PlaybackMode::OneShot => return None,
+                PlaybackMode::Looping => {
+                    let loop_length = (frame.len() as f32 - start).max(1.0);
+                    next = start + (next - frame.len() as f32) % loop_length;
+                }
+            }
+        }
+        Some(next)
+    }
+}
+
+/// How a multi-channel WAV is folded down to the mono signal
+/// `CustomWavetable` requires.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChannelConversion {
+    /// Sums all channels and divides by the channel count. For stereo,
+    /// `mono[i] = (left[i] + right[i]) * 0.5`.
+    #[default]
+    MixDown,
+    /// Keeps only the first channel, discarding the rest.
+    LeftOnly,
+    /// Keeps only the second channel. Falls back to the first channel for
+    /// mono files, where there is no second channel to take.
+    RightOnly,
+    /// Extracts the mid (center-panned) content of a stereo file:
+    /// `mid[i] = (left[i] + right[i]) * 0.5`, the same formula as
+    /// `MixDown` but named for its use isolating shared stereo content
+    /// rather than collapsing the file for mono playback.
+    MidSide,
+}
+
+/// The outcome of `load_sample`: where the sample came from, its native
+/// rate versus the synth's, and whether that mismatch needs correcting.
+#[derive(Debug, Clone)]
+pub struct SampleLoadReport {
+    pub path: PathBuf,
+    pub loaded_sample_rate: u32,
+    pub synth_sample_rate: u32,
+    /// True when `loaded_sample_rate != synth_sample_rate`, meaning
+    /// playback at `advance_position`'s implicit 1:1 rate will be
+    /// mistuned unless the caller resamples or retunes the track.
+    pub needs_resample: bool,
+    /// Channel count of the source WAV file, before folding to mono.
+    pub source_channels: u16,
+    /// The `ChannelConversion` applied to fold `source_channels` down to
+    /// the mono `CustomWavetable`. Always `MixDown` for files that were
+    /// already mono, since no folding was needed.
+    pub conversion_applied: ChannelConversion,
+}
+
+/// Loads a WAV file into a single-frame `CustomWavetable`, folding it down
+/// to mono with `conversion` if it has more than one channel, and
+/// reporting whether its sample rate matches `synth_sample_rate` so the
+/// caller can offer to resample before committing to the load.
+pub fn load_sample(
+    path: &Path,
+    synth_sample_rate: u32,
+    conversion: ChannelConversion,
+) -> Result<(CustomWavetable, SampleLoadReport), hound::Error> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+    let loaded_sample_rate = spec.sample_rate;
+    let channels = spec.channels;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let max_amplitude = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|value| value as f32 / max_amplitude))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let samples = fold_channels(&interleaved, channels, conversion);
+
+    let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("sample").to_string();
+    let wavetable = CustomWavetable {
+        name,
+        sample_rate: loaded_sample_rate,
+        frames: vec![samples],
+        start_position: 0.0,
+        playback_mode: PlaybackMode::default(),
+    };
+
+    let report = SampleLoadReport {
+        path: path.to_path_buf(),
+        loaded_sample_rate,
+        synth_sample_rate,
+        needs_resample: loaded_sample_rate != synth_sample_rate,
+        source_channels: channels,
+        conversion_applied: if channels > 1 { conversion } else { ChannelConversion::MixDown },
+    };
+
+    Ok((wavetable, report))
+}
+
+/// Folds `interleaved` (frames of `channels` samples each) down to mono
+/// per `conversion`. Returns `interleaved` unchanged for mono input.
+fn fold_channels(interleaved: &[f32], channels: u16, conversion: ChannelConversion) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    let channels = channels as usize;
+    interleaved
+        .chunks(channels)
+        .map(|frame| match conversion {
+            ChannelConversion::MixDown => frame.iter().sum::<f32>() / channels as f32,
+            ChannelConversion::LeftOnly => frame[0],
+            ChannelConversion::RightOnly => frame.get(1).copied().unwrap_or(frame[0]),
+            ChannelConversion::MidSide => (frame[0] + frame.get(1).copied().unwrap_or(frame[0])) * 0.5,
+        })
+        .collect()
+}
+
+impl CustomWavetable {
+    /// Removes silence from both ends of every frame, using `threshold` as
+    /// the absolute amplitude below which a sample counts as silence.
+    pub fn trim(&mut self, threshold: f32) {
+        for frame in &mut self.frames {
+            let start = frame.iter().position(|sample| sample.abs() > threshold).unwrap_or(0);
+            let end = frame
+                .iter()
+                .rposition(|sample| sample.abs() > threshold)
+                .map(|index| index + 1)
+                .unwrap_or(frame.len());
+            *frame = frame[start.min(end)..end].to_vec();
+        }
+    }
+
+    /// Blends `crossfade_samples` samples at each frame's loop point to
+    /// eliminate the click caused by an amplitude mismatch between the
+    /// loop's end and its start. This `CustomWavetable` has no separate
+    /// loop-end point (`advance_position` always loops back to
+    /// `start_position` at the end of the frame), so the loop end is each
+    /// frame's last sample and the loop start is `start_position`'s sample
+    /// offset. For position `i` in `0..crossfade_samples`, blends the
+    /// sample `crossfade_samples` before the end with the one `i` samples
+    /// into the loop.
+    pub fn apply_loop_crossfade(&mut self, crossfade_samples: usize) {
+        for frame in &mut self.frames {
+            let loop_start = (self.start_position.clamp(0.0, 1.0) * frame.len() as f32) as usize;
+            let loop_end = frame.len();
+            let crossfade_samples = crossfade_samples.min(loop_end.saturating_sub(loop_start));
+            if crossfade_samples == 0 {
+                continue;
+            }
+            for i in 0..crossfade_samples {
+                let end_index = loop_end - crossfade_samples + i;
+                let start_index = loop_start + i;
+                let t = i as f32 / crossfade_samples as f32;
+                frame[end_index] = frame[end_index] * (1.0 - t) + frame[start_index] * t;
+            }
+        }
+    }
+
+    /// Scales every frame so its peak absolute amplitude is `1.0`, using
+    /// the global peak across all frames so relative levels between frames
+    /// are preserved.
+    pub fn normalize(&mut self) {
+        let peak = self
+            .frames
+            .iter()
+            .flat_map(|frame| frame.iter())
+            .fold(0.0f32, |max, sample| max.max(sample.abs()));
+        if peak <= f32::EPSILON {
+            return;
+        }
+        let gain = 1.0 / peak;
+        for frame in &mut self.frames {
+            for sample in frame.iter_mut() {
+                *sample *= gain;
+            }
+        }
+    }
+
+    /// Subtracts each frame's own mean from every sample in it, removing
+    /// the DC bias room recordings and some synthesized samples carry,
+    /// which otherwise shows up as low-frequency rumble and can destabilize
+    /// a downstream filter. Frames are corrected independently rather than
+    /// by one wavetable-wide mean, since wavetable-morph frames can each
+    /// have their own bias.
+    pub fn remove_dc_offset(&mut self) {
+        for frame in &mut self.frames {
+            if frame.is_empty() {
+                continue;
+            }
+            let mean = frame.iter().sum::<f32>() / frame.len() as f32;
+            for sample in frame.iter_mut() {
+                *sample -= mean;
+            }
+        }
+    }
+
+    /// Measures this wavetable's worst-case (largest-magnitude) per-frame DC
+    /// bias in dBFS, for display alongside the "Remove DC Offset" button.
+    /// Returns `f32::NEG_INFINITY` for an empty wavetable or one with no
+    /// measurable bias.
+    pub fn dc_offset_dbfs(&self) -> f32 {
+        let worst_offset = self
+            .frames
+            .iter()
+            .filter(|frame| !frame.is_empty())
+            .map(|frame| (frame.iter().sum::<f32>() / frame.len() as f32).abs())
+            .fold(0.0f32, f32::max);
+        if worst_offset <= f32::EPSILON {
+            return f32::NEG_INFINITY;
+        }
+        20.0 * worst_offset.log10()
+    }
+
+    /// Writes every frame back-to-back as a mono, 32-bit float WAV at this
+    /// wavetable's stored `sample_rate`, for round-tripping edits made via
+    /// the drawing editor, trimming, or reversing back out to other tools.
+    pub fn export_wav(&self, path: &Path) -> Result<(), hound::Error> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(path, spec)?;
+        for frame in &self.frames {
+            for &sample in frame {
+                writer.write_sample(sample)?;
+            }
+        }
+        writer.finalize()
+    }
+
+    /// Resamples the first frame down to exactly `cycle_length` points
+    /// spanning its full length and writes that as a mono, 32-bit float
+    /// WAV, for exporting a single cycle to other wavetable synths.
+    pub fn export_as_one_cycle(&self, path: &Path, cycle_length: usize) -> Result<(), hound::Error> {
+        let frame = self.frames.first().map(Vec::as_slice).unwrap_or(&[]);
+        let mut cycle = Vec::with_capacity(cycle_length);
+        let last_index = frame.len().saturating_sub(1) as f32;
+        for i in 0..cycle_length {
+            let position = if cycle_length <= 1 { 0.0 } else { i as f32 / (cycle_length - 1) as f32 * last_index };
+            cycle.push(super::audio::interpolate_sample(frame, position, &super::audio::InterpolationQuality::Cubic));
+        }
+
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(path, spec)?;
+        for sample in cycle {
+            writer.write_sample(sample)?;
+        }
+        writer.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_channels_mixes_down_stereo_interleaved_data() {
+        let interleaved = [1.0, -1.0, 0.5, 0.5, 0.0, 2.0];
+        let mono = fold_channels(&interleaved, 2, ChannelConversion::MixDown);
+        assert_eq!(mono, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn fold_channels_keeps_left_only() {
+        let interleaved = [1.0, -1.0, 0.5, 0.5, 0.0, 2.0];
+        let left = fold_channels(&interleaved, 2, ChannelConversion::LeftOnly);
+        assert_eq!(left, vec![1.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn fold_channels_keeps_right_only() {
+        let interleaved = [1.0, -1.0, 0.5, 0.5, 0.0, 2.0];
+        let right = fold_channels(&interleaved, 2, ChannelConversion::RightOnly);
+        assert_eq!(right, vec![-1.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    fn fold_channels_passes_mono_through_unchanged() {
+        let mono = [1.0, -1.0, 0.5];
+        assert_eq!(fold_channels(&mono, 1, ChannelConversion::MixDown), mono.to_vec());
+    }
+
+    #[test]
+    fn remove_dc_offset_zeroes_the_mean_of_an_offset_sample() {
+        let mut wavetable = CustomWavetable {
+            frames: vec![vec![0.5, 0.5, 0.5, 0.5]],
+            ..Default::default()
+        };
+
+        wavetable.remove_dc_offset();
+
+        let frame = &wavetable.frames[0];
+        let mean = frame.iter().sum::<f32>() / frame.len() as f32;
+        assert!(mean.abs() < f32::EPSILON, "expected zero-mean frame, got mean {mean}");
+        assert!(frame.iter().all(|&sample| sample.abs() < f32::EPSILON));
+    }
+}
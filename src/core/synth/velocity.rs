@@ -0,0 +1,72 @@
+/// How raw MIDI velocity (0-127) maps to output amplitude (0.0-1.0).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum VelocityLaw {
+    /// `velocity / 127`, unchanged from the original behavior.
+    #[default]
+    Linear,
+    /// Linear in decibels: velocity 1 maps to `min_db`, velocity 127 maps
+    /// to 0 dB. Matches how real synthesizers perceptually scale velocity,
+    /// since the ear hears loudness logarithmically rather than linearly.
+    DbLinear { min_db: f32 },
+}
+
+/// How note velocity scales a voice's attack and release times, on top of
+/// `VelocityLaw`'s amplitude curve. Modeled on acoustic instruments, where
+/// hard keystrokes produce snappier attacks (and, on some instruments,
+/// slightly longer releases) than soft ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VelocityScalingConfig {
+    /// How much slower a velocity-1 attack is than a velocity-127 attack,
+    /// in multiples of the base attack time. `0.0` disables the effect
+    /// (every velocity attacks at the base time); `1.0` means velocity 1
+    /// attacks 4x slower than velocity 127, per `attack_scale_for`.
+    pub velocity_to_attack_scale: f32,
+    /// Same idea for release time: at `1.0`, velocity 127 releases up to
+    /// 2x slower than velocity 1, matching how harder playing can produce
+    /// a longer-ringing release on some instruments.
+    pub velocity_to_release_scale: f32,
+}
+
+impl Default for VelocityScalingConfig {
+    fn default() -> Self {
+        Self {
+            velocity_to_attack_scale: 0.0,
+            velocity_to_release_scale: 0.0,
+        }
+    }
+}
+
+impl VelocityScalingConfig {
+    /// Multiplier applied to the base attack time for `velocity` (1-127).
+    /// Soft keystrokes get a longer, slower attack; `velocity_to_attack_scale`
+    /// of `1.0` stretches velocity-1 attacks to 4x the base time.
+    pub fn attack_scale_for(&self, velocity: u8) -> f32 {
+        let normalized = (velocity.max(1) as f32 / 127.0).clamp(0.0, 1.0);
+        let max_scale = 1.0 + self.velocity_to_attack_scale * 3.0;
+        max_scale + (1.0 - max_scale) * normalized
+    }
+
+    /// Multiplier applied to the base release time for `velocity` (1-127).
+    /// Harder keystrokes get a longer release.
+    pub fn release_scale_for(&self, velocity: u8) -> f32 {
+        let normalized = (velocity.max(1) as f32 / 127.0).clamp(0.0, 1.0);
+        1.0 + self.velocity_to_release_scale * normalized
+    }
+}
+
+/// Converts a raw MIDI velocity into an amplitude (0.0-1.0) under `law`.
+/// Velocity `0` always produces silence, regardless of `law` (MIDI channel
+/// voice messages use a note-on with velocity 0 as a note-off).
+pub fn compute_velocity_amplitude(raw: u8, law: &VelocityLaw) -> f32 {
+    if raw == 0 {
+        return 0.0;
+    }
+    let normalized = raw as f32 / 127.0;
+    match law {
+        VelocityLaw::Linear => normalized,
+        VelocityLaw::DbLinear { min_db } => {
+            let db = min_db + (0.0 - min_db) * ((raw as f32 - 1.0) / 126.0);
+            10.0f32.powf(db / 20.0)
+        }
+    }
+}
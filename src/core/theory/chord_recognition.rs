@@ -0,0 +1,125 @@
+/// The quality (interval structure) of a recognized chord, independent of
+/// its root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Dom7,
+    Maj7,
+    Min7,
+    Dim,
+    Aug,
+    Sus2,
+    Sus4,
+}
+
+impl ChordQuality {
+    /// Semitone intervals from the root that define this quality.
+    fn intervals(self) -> &'static [u8] {
+        match self {
+            ChordQuality::Major => &[0, 4, 7],
+            ChordQuality::Minor => &[0, 3, 7],
+            ChordQuality::Dom7 => &[0, 4, 7, 10],
+            ChordQuality::Maj7 => &[0, 4, 7, 11],
+            ChordQuality::Min7 => &[0, 3, 7, 10],
+            ChordQuality::Dim => &[0, 3, 6],
+            ChordQuality::Aug => &[0, 4, 8],
+            ChordQuality::Sus2 => &[0, 2, 7],
+            ChordQuality::Sus4 => &[0, 5, 7],
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ChordQuality::Major => "",
+            ChordQuality::Minor => "m",
+            ChordQuality::Dom7 => "7",
+            ChordQuality::Maj7 => "maj7",
+            ChordQuality::Min7 => "m7",
+            ChordQuality::Dim => "dim",
+            ChordQuality::Aug => "aug",
+            ChordQuality::Sus2 => "sus2",
+            ChordQuality::Sus4 => "sus4",
+        }
+    }
+}
+
+/// Qualities `ChordRecognizer::identify` matches against, ordered so that
+/// more specific (longer-interval) qualities are tried before the triads
+/// they extend, since e.g. a dominant 7th also contains a major triad.
+const QUALITIES: [ChordQuality; 9] = [
+    ChordQuality::Dom7,
+    ChordQuality::Maj7,
+    ChordQuality::Min7,
+    ChordQuality::Major,
+    ChordQuality::Minor,
+    ChordQuality::Dim,
+    ChordQuality::Aug,
+    ChordQuality::Sus2,
+    ChordQuality::Sus4,
+];
+
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+/// A recognized chord: its root pitch class, quality, and inversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChordName {
+    /// Pitch class of the root, `0` (C) through `11` (B).
+    pub root: u8,
+    pub quality: ChordQuality,
+    /// `0` = root position, `1` = first inversion (third in the bass), etc.
+    pub inversion: u8,
+    /// Pitch class of the lowest sounding note, for the "/E"-style slash
+    /// notation `Display` prints when it differs from `root`.
+    bass: u8,
+}
+
+impl std::fmt::Display for ChordName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", NOTE_NAMES[self.root as usize], self.quality.label())?;
+        if self.bass != self.root {
+            write!(f, "/{}", NOTE_NAMES[self.bass as usize])?;
+        }
+        Ok(())
+    }
+}
+
+/// Identifies chords from a set of currently-held MIDI notes by matching
+/// their pitch-class intervals against `QUALITIES`.
+pub struct ChordRecognizer;
+
+impl ChordRecognizer {
+    /// Extracts pitch classes from `notes`, dedupes and sorts them, and
+    /// tries every rotation against every quality in `QUALITIES` (trying
+    /// each note in turn as the candidate root) until one matches.
+    /// Returns `None` for fewer than 3 distinct pitch classes, or if no
+    /// quality's interval set matches.
+    pub fn identify(notes: &[u8]) -> Option<ChordName> {
+        if notes.is_empty() {
+            return None;
+        }
+        let bass = notes.iter().min().copied().unwrap_or(0) % 12;
+
+        let mut pitch_classes: Vec<u8> = notes.iter().map(|note| note % 12).collect();
+        pitch_classes.sort_unstable();
+        pitch_classes.dedup();
+        if pitch_classes.len() < 3 {
+            return None;
+        }
+
+        for &candidate_root in &pitch_classes {
+            let relative: std::collections::BTreeSet<u8> =
+                pitch_classes.iter().map(|&pitch_class| (pitch_class + 12 - candidate_root) % 12).collect();
+
+            for &quality in &QUALITIES {
+                let intervals: std::collections::BTreeSet<u8> = quality.intervals().iter().copied().collect();
+                if relative == intervals {
+                    let bass_interval = (bass + 12 - candidate_root) % 12;
+                    let inversion = quality.intervals().iter().position(|&interval| interval == bass_interval).unwrap_or(0) as u8;
+                    return Some(ChordName { root: candidate_root, quality, inversion, bass });
+                }
+            }
+        }
+        None
+    }
+}
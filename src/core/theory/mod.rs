@@ -0,0 +1,90 @@
+pub mod chord_recognition;
+
+/// A named set of scale-step intervals (in semitones from the root),
+/// used by `Scale::quantize_note` to snap incoming notes into a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScaleType {
+    Major,
+    Minor,
+    Pentatonic,
+    Blues,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Locrian,
+    /// Arbitrary intervals (semitones, within one octave) from the root.
+    Custom(Vec<u8>),
+}
+
+impl ScaleType {
+    /// The semitone offsets from the root that belong to this scale.
+    pub fn intervals(&self) -> &[u8] {
+        match self {
+            ScaleType::Major => &[0, 2, 4, 5, 7, 9, 11],
+            ScaleType::Minor => &[0, 2, 3, 5, 7, 8, 10],
+            ScaleType::Pentatonic => &[0, 2, 4, 7, 9],
+            ScaleType::Blues => &[0, 3, 5, 6, 7, 10],
+            ScaleType::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            ScaleType::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            ScaleType::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            ScaleType::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            ScaleType::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            ScaleType::Custom(intervals) => intervals,
+        }
+    }
+}
+
+/// A `ScaleType` anchored to a root note, used to quantize incoming MIDI
+/// notes into that key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scale {
+    /// Pitch class of the root, `0` (C) through `11` (B).
+    pub root: u8,
+    pub scale_type: ScaleType,
+}
+
+impl Scale {
+    pub fn new(root: u8, scale_type: ScaleType) -> Self {
+        Self { root: root % 12, scale_type }
+    }
+
+    /// Rounds `midi_note` to the nearest pitch class in this scale,
+    /// preserving the original octave. Ties round down (toward the
+    /// nearest lower scale tone).
+    pub fn quantize_note(&self, midi_note: u8) -> u8 {
+        let intervals = self.scale_type.intervals();
+        if intervals.is_empty() {
+            return midi_note;
+        }
+
+        let pitch_class = (midi_note % 12) as i16 - self.root as i16;
+        let pitch_class = pitch_class.rem_euclid(12);
+
+        // Compare against each interval's wrapped neighbors too (interval -
+        // 12 and interval + 12), not just its 0..12 value, so a pitch class
+        // near the octave boundary can snap to a scale tone in the
+        // neighboring octave instead of wrapping all the way to the other
+        // side of this one.
+        let mut best_offset = intervals[0] as i16;
+        let mut best_distance = i16::MAX;
+        for &interval in intervals {
+            for candidate in [interval as i16 - 12, interval as i16, interval as i16 + 12] {
+                let distance = (pitch_class - candidate).abs();
+                if distance < best_distance || (distance == best_distance && candidate < best_offset) {
+                    best_distance = distance;
+                    best_offset = candidate;
+                }
+            }
+        }
+
+        let quantized = midi_note as i16 - pitch_class + best_offset;
+        quantized.clamp(0, 127) as u8
+    }
+}
+
+/// Standalone wrapper matching the common "quantize a note into a scale"
+/// phrasing, delegating to `Scale::quantize_note`.
+pub fn quantize_to_scale(note: u8, root: u8, scale_type: ScaleType) -> u8 {
+    Scale::new(root, scale_type).quantize_note(note)
+}
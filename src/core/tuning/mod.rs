@@ -0,0 +1,117 @@
+/// A microtuning table mapping MIDI note numbers to frequency ratios
+/// relative to the tuning's reference note, loaded from a Scala (`.scl`)
+/// or MTS (MIDI Tuning Standard) file.
+#[derive(Debug, Clone)]
+pub struct Tuning {
+    /// Ratios for one octave, starting after the root (the root itself is
+    /// always ratio 1.0 and isn't stored).
+    degree_ratios: Vec<f64>,
+    pub reference_note: u8,
+    pub reference_frequency_hz: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        // 12-tone equal temperament, expressed the same way a loaded
+        // scale would be.
+        let degree_ratios = (1..=12).map(|n| 2.0f64.powf(n as f64 / 12.0)).collect();
+        Self {
+            degree_ratios,
+            reference_note: 69,
+            reference_frequency_hz: 440.0,
+        }
+    }
+}
+
+impl Tuning {
+    /// Parses a Scala `.scl` file: comment lines start with `!`, the first
+    /// non-comment line is a description, the second is the degree count,
+    /// followed by that many ratio or cents lines.
+    pub fn from_scl(contents: &str) -> Result<Self, String> {
+        let mut lines = contents.lines().filter(|line| !line.trim_start().starts_with('!'));
+        let _description = lines.next().ok_or("missing description line")?;
+        let degree_count: usize = lines
+            .next()
+            .ok_or("missing degree count line")?
+            .trim()
+            .parse()
+            .map_err(|_| "invalid degree count")?;
+
+        let mut degree_ratios = Vec::with_capacity(degree_count);
+        for line in lines.take(degree_count) {
+            degree_ratios.push(parse_scl_degree(line.trim())?);
+        }
+        if degree_ratios.len() != degree_count {
+            return Err("fewer degree lines than declared".to_string());
+        }
+
+        Ok(Self {
+            degree_ratios,
+            reference_note: 69,
+            reference_frequency_hz: 440.0,
+        })
+    }
+
+    /// Parses an MTS bulk dump (single-note tuning format), which encodes
+    /// each of the 128 MIDI notes as an absolute frequency directly rather
+    /// than octave-relative ratios.
+    pub fn from_mts_bulk_dump(bytes: &[u8]) -> Result<Self, String> {
+        // Header (1 sysex start + 6 id bytes) + 128 * 3 frequency bytes + checksum + footer.
+        if bytes.len() < 7 + 128 * 3 {
+            return Err("MTS bulk dump too short".to_string());
+        }
+        let mut frequencies = [0.0f64; 128];
+        for (note, frequency) in frequencies.iter_mut().enumerate() {
+            let offset = 7 + note * 3;
+            let semitone = bytes[offset] as f64;
+            let msb = bytes[offset + 1] as f64;
+            let lsb = bytes[offset + 2] as f64;
+            let fraction = (msb * 128.0 + lsb) / 16384.0;
+            *frequency = 8.1757989156 * 2.0f64.powf((semitone + fraction) / 12.0);
+        }
+
+        let reference_note = 69u8;
+        let reference_frequency_hz = frequencies[reference_note as usize] as f32;
+        let degree_ratios = (1..=12)
+            .map(|offset| frequencies[(reference_note as usize + offset) % 128] / frequencies[reference_note as usize])
+            .collect();
+
+        Ok(Self {
+            degree_ratios,
+            reference_note,
+            reference_frequency_hz,
+        })
+    }
+
+    /// Frequency in Hz for `note`, relative to `reference_note`.
+    pub fn frequency_for_note(&self, note: u8) -> f32 {
+        let steps_from_reference = note as i32 - self.reference_note as i32;
+        let octaves = steps_from_reference.div_euclid(self.degree_ratios.len() as i32);
+        let degree = steps_from_reference.rem_euclid(self.degree_ratios.len() as i32);
+
+        let within_octave_ratio = if degree == 0 {
+            1.0
+        } else {
+            self.degree_ratios[degree as usize - 1]
+        };
+        let octave_ratio = self.degree_ratios.last().copied().unwrap_or(2.0).powi(octaves);
+
+        (self.reference_frequency_hz as f64 * within_octave_ratio * octave_ratio) as f32
+    }
+}
+
+fn parse_scl_degree(line: &str) -> Result<f64, String> {
+    if line.contains('.') {
+        // Per the Scala format, a degree line is a ratio only if it's a
+        // bare integer or an `n/d` fraction; anything with a decimal point
+        // is cents, with no literal "cents" suffix in real files.
+        let cents: f64 = line.parse().map_err(|_| "invalid cents value")?;
+        Ok(2.0f64.powf(cents / 1200.0))
+    } else if let Some((numerator, denominator)) = line.split_once('/') {
+        let numerator: f64 = numerator.trim().parse().map_err(|_| "invalid ratio numerator")?;
+        let denominator: f64 = denominator.trim().parse().map_err(|_| "invalid ratio denominator")?;
+        Ok(numerator / denominator)
+    } else {
+        line.parse().map_err(|_| "invalid ratio".to_string())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod spectrum_bands;
+
+pub use spectrum_bands::{ThirdOctaveAnalyzer, THIRD_OCTAVE_CENTERS_HZ};
@@ -0,0 +1,40 @@
+/// ISO 266 standard third-octave center frequencies, 20 Hz through 20 kHz,
+/// the same 31 bands used by hardware graphic EQs.
+pub const THIRD_OCTAVE_CENTERS_HZ: [f32; 31] = [
+    20.0, 25.0, 31.5, 40.0, 50.0, 63.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 315.0, 400.0, 500.0, 630.0, 800.0, 1000.0, 1250.0,
+    1600.0, 2000.0, 2500.0, 3150.0, 4000.0, 5000.0, 6300.0, 8000.0, 10_000.0, 12_500.0, 16_000.0, 20_000.0,
+];
+
+/// Half a third-octave in factor form (`2^(1/6)`), the band edges either
+/// side of each `THIRD_OCTAVE_CENTERS_HZ` entry.
+const THIRD_OCTAVE_HALF_WIDTH: f32 = 1.122_462_1; // 2f32.powf(1.0 / 6.0)
+
+/// Groups `Analyzer::compute_fft`'s linear-frequency bins into the 31
+/// standard third-octave bands, for a classic graphic-EQ-style display
+/// instead of a raw, hard-to-read FFT spectrum.
+pub struct ThirdOctaveAnalyzer;
+
+impl ThirdOctaveAnalyzer {
+    /// Bins `fft_output` (`[frequency_hz, magnitude]` pairs, as returned by
+    /// `Analyzer::compute_fft`) into `THIRD_OCTAVE_CENTERS_HZ`'s 31 bands,
+    /// returning each band's RMS magnitude. `sample_rate` isn't needed to
+    /// bin `fft_output` (its bins are already labeled in Hz) but is kept in
+    /// the signature to mirror `Analyzer::compute_fft`'s call shape.
+    pub fn compute(fft_output: &[[f32; 2]], _sample_rate: f32) -> [f32; 31] {
+        let mut bands = [0.0f32; 31];
+        for (band, &center_hz) in bands.iter_mut().zip(THIRD_OCTAVE_CENTERS_HZ.iter()) {
+            let low_hz = center_hz / THIRD_OCTAVE_HALF_WIDTH;
+            let high_hz = center_hz * THIRD_OCTAVE_HALF_WIDTH;
+            let mut sum_squares = 0.0;
+            let mut count = 0;
+            for &[frequency_hz, magnitude] in fft_output {
+                if frequency_hz >= low_hz && frequency_hz < high_hz {
+                    sum_squares += magnitude * magnitude;
+                    count += 1;
+                }
+            }
+            *band = if count > 0 { (sum_squares / count as f32).sqrt() } else { 0.0 };
+        }
+        bands
+    }
+}
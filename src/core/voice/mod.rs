@@ -0,0 +1,60 @@
+pub mod voice_pool;
+
+use serde::{Deserialize, Serialize};
+
+/// Which currently-sounding note to steal when a new note-on would exceed
+/// the polyphony limit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum NotePriority {
+    /// Keep the highest-pitched active note sounding, stealing from below it.
+    Highest,
+    /// Keep the lowest-pitched active note sounding, stealing from above it.
+    Lowest,
+    /// Steal whichever note was played first (oldest), keeping recent
+    /// notes alive longest.
+    #[default]
+    LastPlayed,
+    /// Monophonic retarget mode: stealing falls back to `LastPlayed`'s
+    /// behavior, but `Synth::note_off` additionally retargets to the
+    /// previous still-held note (via `Synth::held_notes_stack`) instead of
+    /// going silent when the most recent note is released.
+    LastNoteMonophonic,
+}
+
+impl NotePriority {
+    /// True for priorities under which `Synth::held_notes_stack` tracks
+    /// held notes and `Synth::note_off` retargets the freed voice to
+    /// whichever held note `retarget_from` picks, instead of letting it go
+    /// silent. `LastPlayed` has no single-voice retarget behavior of its
+    /// own, so it's excluded.
+    pub fn retargets_held_notes(self) -> bool {
+        matches!(self, NotePriority::Highest | NotePriority::Lowest | NotePriority::LastNoteMonophonic)
+    }
+
+    /// Picks which still-held note (if any) a freed voice should retarget
+    /// to, per this priority: `Highest`/`Lowest` pick the extreme of
+    /// `held_notes`, `LastNoteMonophonic` picks the most recently pressed
+    /// (the stack's last element). Only meaningful when
+    /// `retargets_held_notes` is true; other priorities return `None`.
+    pub fn retarget_from(self, held_notes: &[u8]) -> Option<u8> {
+        match self {
+            NotePriority::Highest => held_notes.iter().max().copied(),
+            NotePriority::Lowest => held_notes.iter().min().copied(),
+            NotePriority::LastNoteMonophonic => held_notes.last().copied(),
+            NotePriority::LastPlayed => None,
+        }
+    }
+}
+
+/// Picks which of `active_notes` (note number, order-played index) to
+/// steal to make room for a new note, per `priority`.
+pub fn choose_note_to_steal(active_notes: &[(u8, u64)], priority: NotePriority) -> Option<u8> {
+    match priority {
+        NotePriority::Highest => active_notes.iter().min_by_key(|(note, _)| *note).map(|(note, _)| *note),
+        NotePriority::Lowest => active_notes.iter().max_by_key(|(note, _)| *note).map(|(note, _)| *note),
+        NotePriority::LastPlayed | NotePriority::LastNoteMonophonic => active_notes
+            .iter()
+            .min_by_key(|(_, played_at)| *played_at)
+            .map(|(note, _)| *note),
+    }
+}
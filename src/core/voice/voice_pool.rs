@@ -0,0 +1,164 @@
+//! A fixed-capacity, slot-indexed alternative to `Synth`'s
+//! `HashMap<u8, Voice>` voice storage.
+//!
+//! `Synth` already enforces a polyphony limit and voice stealing via
+//! `Synth::max_polyphony`/`NotePriority`/[`choose_note_to_steal`](super::choose_note_to_steal),
+//! keyed by MIDI note number. `VoicePool` instead pre-allocates `capacity`
+//! fixed slots indexed by position, each tracking its own ADSR-style
+//! `NoteState`, so a caller that wants slot-stable voice identity (e.g. for
+//! per-voice UI meters, or a fixed-size voice-to-output-channel mapping)
+//! doesn't have to derive it from a `HashMap`'s iteration order. It is not
+//! wired into `Synth::get_sample`, which continues to drive its own
+//! `voices: HashMap<u8, Voice>`.
+
+use super::{choose_note_to_steal, NotePriority};
+
+/// A voice slot's position in its amplitude envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoteState {
+    #[default]
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// How `VoicePool::allocate_voice` picks a slot to steal when every slot is
+/// already active. Mirrors `NotePriority`, minus the monophonic-retarget
+/// variant, which is a `Synth`-level behavior rather than a stealing policy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VoiceStealPolicy {
+    Highest,
+    Lowest,
+    Oldest,
+}
+
+impl From<NotePriority> for VoiceStealPolicy {
+    fn from(priority: NotePriority) -> Self {
+        match priority {
+            NotePriority::Highest => VoiceStealPolicy::Highest,
+            NotePriority::Lowest => VoiceStealPolicy::Lowest,
+            NotePriority::LastPlayed | NotePriority::LastNoteMonophonic => VoiceStealPolicy::Oldest,
+        }
+    }
+}
+
+/// One fixed voice slot's full per-note state.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Voice {
+    pub midi_note: u8,
+    pub frequency: f32,
+    /// Running phase for up to three oscillators, indexed the same as
+    /// `Synth::oscillator_templates`.
+    pub phase: [f32; 3],
+    pub envelope_state: NoteState,
+    /// Seconds elapsed since `envelope_state` last changed.
+    pub time_in_state: f32,
+    pub velocity: f32,
+    pub active: bool,
+    /// Monotonic order this slot was (re)allocated in, used by
+    /// `VoiceStealPolicy::Oldest`.
+    allocated_at: u64,
+}
+
+/// A fixed-capacity set of voice slots with explicit envelope-state
+/// tracking, indexed by slot position rather than by MIDI note.
+pub struct VoicePool {
+    capacity: usize,
+    voices: Vec<Voice>,
+    next_allocation_order: u64,
+}
+
+impl VoicePool {
+    /// Creates a pool of `capacity` silent slots.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, voices: vec![Voice::default(); capacity], next_allocation_order: 0 }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn voices(&self) -> &[Voice] {
+        &self.voices
+    }
+
+    /// Finds a free slot for `note`, or steals one per `steal` if every
+    /// slot is active. Returns the slot index that now holds the voice.
+    pub fn allocate_voice(&mut self, note: u8, freq: f32, vel: f32, steal: VoiceStealPolicy) -> usize {
+        let index = self
+            .voices
+            .iter()
+            .position(|voice| !voice.active)
+            .unwrap_or_else(|| self.choose_slot_to_steal(steal));
+
+        let allocated_at = self.next_allocation_order;
+        self.next_allocation_order += 1;
+        self.voices[index] = Voice {
+            midi_note: note,
+            frequency: freq,
+            phase: [0.0; 3],
+            envelope_state: NoteState::Attack,
+            time_in_state: 0.0,
+            velocity: vel,
+            active: true,
+            allocated_at,
+        };
+        index
+    }
+
+    fn choose_slot_to_steal(&self, steal: VoiceStealPolicy) -> usize {
+        let candidates: Vec<(u8, u64)> =
+            self.voices.iter().map(|voice| (voice.midi_note, voice.allocated_at)).collect();
+        let note_priority = match steal {
+            VoiceStealPolicy::Highest => NotePriority::Highest,
+            VoiceStealPolicy::Lowest => NotePriority::Lowest,
+            VoiceStealPolicy::Oldest => NotePriority::LastPlayed,
+        };
+        let stolen_note = choose_note_to_steal(&candidates, note_priority).unwrap_or(candidates[0].0);
+        self.voices
+            .iter()
+            .position(|voice| voice.midi_note == stolen_note)
+            .unwrap_or(0)
+    }
+
+    /// Moves every active slot matching `note` into `NoteState::Release`,
+    /// leaving its envelope to fade out rather than cutting it off.
+    pub fn release_voice(&mut self, note: u8) {
+        for voice in self.voices.iter_mut() {
+            if voice.active && voice.midi_note == note && voice.envelope_state != NoteState::Release {
+                voice.envelope_state = NoteState::Release;
+                voice.time_in_state = 0.0;
+            }
+        }
+    }
+
+    /// Advances every active slot's envelope state machine by `dt` seconds,
+    /// transitioning `Attack` -> `Decay` -> `Sustain` once their durations
+    /// elapse, and `Release` -> `Idle` (freeing the slot) once it finishes.
+    pub fn advance(&mut self, dt: f32, attack_seconds: f32, decay_seconds: f32, release_seconds: f32) {
+        for voice in self.voices.iter_mut() {
+            if !voice.active {
+                continue;
+            }
+            voice.time_in_state += dt;
+            match voice.envelope_state {
+                NoteState::Attack if voice.time_in_state >= attack_seconds => {
+                    voice.envelope_state = NoteState::Decay;
+                    voice.time_in_state = 0.0;
+                }
+                NoteState::Decay if voice.time_in_state >= decay_seconds => {
+                    voice.envelope_state = NoteState::Sustain;
+                    voice.time_in_state = 0.0;
+                }
+                NoteState::Release if voice.time_in_state >= release_seconds => {
+                    voice.envelope_state = NoteState::Idle;
+                    voice.time_in_state = 0.0;
+                    voice.active = false;
+                }
+                _ => {}
+            }
+        }
+    }
+}
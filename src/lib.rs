@@ -0,0 +1,4 @@
+pub mod app;
+pub mod core;
+pub mod ui;
+pub mod utils;
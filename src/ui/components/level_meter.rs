@@ -0,0 +1,39 @@
+//! A small vertical peak level meter, shared by the header's master meter
+//! and each `OscillatorPanel`'s per-oscillator meter.
+
+/// Below this, the meter reads as silence rather than `-inf` dBFS.
+const FLOOR_DB: f32 = -60.0;
+/// Above this, the bar is considered full even before clipping.
+const CEILING_DB: f32 = 0.0;
+
+fn linear_to_db(linear: f32) -> f32 {
+    if linear <= 0.0 {
+        FLOOR_DB
+    } else {
+        20.0 * linear.log10()
+    }
+}
+
+/// Draws a vertical bar for `peak` (linear amplitude, 1.0 = 0 dBFS): green
+/// below -6 dBFS, yellow up to 0 dBFS, red once `clipped` is latched.
+/// Returns `true` if the user clicked a clipped meter to reset it.
+pub fn show(ui: &mut egui::Ui, peak: f32, clipped: bool) -> bool {
+    let db = linear_to_db(peak).clamp(FLOOR_DB, CEILING_DB);
+    let fraction = (db - FLOOR_DB) / (CEILING_DB - FLOOR_DB);
+
+    let color = if clipped {
+        egui::Color32::from_rgb(220, 50, 50)
+    } else if db > -6.0 {
+        egui::Color32::from_rgb(220, 180, 0)
+    } else {
+        egui::Color32::from_rgb(60, 180, 60)
+    };
+
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(12.0, 48.0), egui::Sense::click());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(30));
+    let filled = rect.with_min_y(rect.max.y - rect.height() * fraction.clamp(0.0, 1.0));
+    ui.painter().rect_filled(filled, 2.0, color);
+
+    clipped && response.clicked()
+}
@@ -0,0 +1,4 @@
+pub mod level_meter;
+pub mod piano_keyboard;
+pub mod routing_diagram;
+pub mod wavetable_editor;
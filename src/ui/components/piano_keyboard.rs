@@ -0,0 +1,138 @@
+//! An on-screen piano keyboard with a fading "note trail": each key
+//! brightens to full intensity on note-on and decays back toward its
+//! resting color over the following frames, so recently played notes stay
+//! visible for a moment after they're released rather than vanishing
+//! instantly.
+
+/// One slot per MIDI note (0-127).
+const KEY_COUNT: usize = 128;
+/// Brightness multiplier applied per frame; at 60 FPS, 0.995 halves a key's
+/// brightness in about 2.3 s and reaches `MIN_VISIBLE_BRIGHTNESS` after
+/// about 15 s.
+const DECAY_PER_FRAME: f32 = 0.995;
+/// Below this a key is indistinguishable from resting, so snap it to 0.0
+/// and stop requesting repaints on its account.
+const MIN_VISIBLE_BRIGHTNESS: f32 = 0.01;
+
+const WHITE_KEY_SIZE: egui::Vec2 = egui::vec2(18.0, 70.0);
+const BLACK_KEY_SIZE: egui::Vec2 = egui::vec2(12.0, 44.0);
+const RESTING_WHITE: egui::Color32 = egui::Color32::from_gray(230);
+const RESTING_BLACK: egui::Color32 = egui::Color32::from_gray(30);
+const ACTIVE_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 200, 255);
+
+/// Whether `note % 12` falls on a black (sharp) key.
+fn is_black_key(note: u8) -> bool {
+    matches!(note % 12, 1 | 3 | 6 | 8 | 10)
+}
+
+fn mix(resting: egui::Color32, brightness: f32) -> egui::Color32 {
+    let t = brightness.clamp(0.0, 1.0);
+    egui::Color32::from_rgb(
+        (resting.r() as f32 + (ACTIVE_COLOR.r() as f32 - resting.r() as f32) * t) as u8,
+        (resting.g() as f32 + (ACTIVE_COLOR.g() as f32 - resting.g() as f32) * t) as u8,
+        (resting.b() as f32 + (ACTIVE_COLOR.b() as f32 - resting.b() as f32) * t) as u8,
+    )
+}
+
+/// Tracks a fading `brightness` per MIDI note so `show` can render both
+/// currently-held notes (brightness freshly set to 1.0) and a short trail of
+/// recently released ones (brightness still decaying toward 0.0) in a single
+/// pass, without needing a separate "is this note currently held" set.
+pub struct PianoKeyboard {
+    brightness: [f32; KEY_COUNT],
+}
+
+impl PianoKeyboard {
+    pub fn new() -> Self {
+        Self { brightness: [0.0; KEY_COUNT] }
+    }
+
+    /// Flashes `note` to full brightness; call this on note-on.
+    pub fn note_on(&mut self, note: u8) {
+        if let Some(slot) = self.brightness.get_mut(note as usize) {
+            *slot = 1.0;
+        }
+    }
+
+    /// Decays every key's brightness by one frame, requesting another
+    /// repaint while any key is still visibly lit. `egui` only repaints on
+    /// input or an explicit request, so the trail would otherwise freeze at
+    /// whatever brightness it had when the user last moved the mouse.
+    pub fn update_decay(&mut self, ctx: &egui::Context) {
+        let mut any_visible = false;
+        for brightness in &mut self.brightness {
+            if *brightness > 0.0 {
+                *brightness *= DECAY_PER_FRAME;
+                if *brightness < MIN_VISIBLE_BRIGHTNESS {
+                    *brightness = 0.0;
+                } else {
+                    any_visible = true;
+                }
+            }
+        }
+        if any_visible {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Draws one key per note in `first_note..=last_note`, mixing each
+    /// key's resting color toward `ACTIVE_COLOR` by its current brightness.
+    /// White keys are laid out first in an even row; black keys are drawn
+    /// on top, offset into the gap between their neighboring white keys.
+    pub fn show(&self, ui: &mut egui::Ui, first_note: u8, last_note: u8) {
+        self.show_with_range_mask(ui, first_note, last_note, |_| true);
+    }
+
+    /// Same as `show`, but keys for which `in_range` returns `false` are
+    /// drawn dimmed, for visualizing a selected oscillator's
+    /// `midi_note_low..=midi_note_high` split-keyboard range.
+    pub fn show_with_range_mask(&self, ui: &mut egui::Ui, first_note: u8, last_note: u8, in_range: impl Fn(u8) -> bool) {
+        let white_notes: Vec<u8> = (first_note..=last_note).filter(|note| !is_black_key(*note)).collect();
+        let width = WHITE_KEY_SIZE.x * white_notes.len().max(1) as f32;
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(width, WHITE_KEY_SIZE.y), egui::Sense::hover());
+        let painter = ui.painter();
+
+        for (index, &note) in white_notes.iter().enumerate() {
+            let key_rect = egui::Rect::from_min_size(
+                rect.min + egui::vec2(WHITE_KEY_SIZE.x * index as f32, 0.0),
+                WHITE_KEY_SIZE,
+            );
+            let color = dim_if_out_of_range(mix(RESTING_WHITE, self.brightness[note as usize]), in_range(note));
+            painter.rect_filled(key_rect, 1.0, color);
+            painter.rect_stroke(key_rect, 1.0, egui::Stroke::new(1.0, egui::Color32::from_gray(120)));
+        }
+
+        for (index, &note) in white_notes.iter().enumerate() {
+            if note == last_note || !is_black_key(note + 1) {
+                continue;
+            }
+            let black_note = note + 1;
+            let center_x = rect.min.x + WHITE_KEY_SIZE.x * (index as f32 + 1.0);
+            let key_rect = egui::Rect::from_center_size(
+                egui::pos2(center_x, rect.min.y + BLACK_KEY_SIZE.y / 2.0),
+                BLACK_KEY_SIZE,
+            );
+            let color = dim_if_out_of_range(mix(RESTING_BLACK, self.brightness[black_note as usize]), in_range(black_note));
+            painter.rect_filled(key_rect, 1.0, color);
+        }
+    }
+}
+
+/// Blends `color` halfway toward mid-gray when `in_range` is `false`,
+/// visually marking a key as outside an oscillator's configured note range.
+fn dim_if_out_of_range(color: egui::Color32, in_range: bool) -> egui::Color32 {
+    if in_range {
+        return color;
+    }
+    egui::Color32::from_rgb(
+        ((color.r() as u16 + 128) / 2) as u8,
+        ((color.g() as u16 + 128) / 2) as u8,
+        ((color.b() as u16 + 128) / 2) as u8,
+    )
+}
+
+impl Default for PianoKeyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
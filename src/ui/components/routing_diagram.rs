@@ -0,0 +1,82 @@
+//! A small signal-flow diagram showing how a patch's oscillators combine,
+//! shared between any panel that exposes `OscillatorCombinationMode`.
+
+use crate::core::oscillator::{Oscillator, OscillatorCombinationMode};
+
+const BOX_SIZE: egui::Vec2 = egui::vec2(64.0, 28.0);
+const ROW_SPACING: f32 = 40.0;
+const COLUMN_SPACING: f32 = 110.0;
+
+/// Draws the oscillator routing topology for `combination_mode`: one box
+/// per oscillator in `oscillator_templates`, converging into a "+" mixer
+/// and then "Output". Oscillators with `volume == 0.0` are drawn grayed
+/// out, since they contribute nothing to the mix. Returns the index of an
+/// oscillator box the user clicked, if any, for the caller to act on (this
+/// app has no cross-panel navigation state to jump to that oscillator's
+/// settings with, so callers can only use it for in-panel highlighting).
+pub fn show(ui: &mut egui::Ui, combination_mode: &OscillatorCombinationMode, oscillator_templates: &[Oscillator]) -> Option<usize> {
+    let row_count = oscillator_templates.len().max(1) as f32;
+    let height = ROW_SPACING * row_count + 20.0;
+    let width = COLUMN_SPACING * 2.0 + BOX_SIZE.x;
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+
+    let osc_x = rect.min.x + BOX_SIZE.x / 2.0 + 4.0;
+    let mixer_x = osc_x + COLUMN_SPACING;
+    let output_x = mixer_x + COLUMN_SPACING;
+    let center_y = rect.center().y;
+
+    let mixer_center = egui::pos2(mixer_x, center_y);
+    let output_center = egui::pos2(output_x, center_y);
+
+    let mut clicked = None;
+    for (index, oscillator) in oscillator_templates.iter().enumerate() {
+        let row_y = rect.min.y + ROW_SPACING * (index as f32 + 0.5);
+        let center = egui::pos2(osc_x, row_y);
+        let grayed_out = oscillator.volume <= 0.0;
+        let label = match combination_mode {
+            OscillatorCombinationMode::Mix => format!("Osc {}", index + 1),
+            OscillatorCombinationMode::Additive(partials) => partials
+                .get(index)
+                .map(|partial| format!("Harm {}", partial.harmonic_number))
+                .unwrap_or_else(|| format!("Osc {}", index + 1)),
+        };
+
+        ui.painter().line_segment([center, mixer_center], egui::Stroke::new(1.5, line_color(grayed_out)));
+        let box_rect = draw_box(ui.painter(), center, &label, grayed_out);
+        if box_rect.contains(pointer_position(ui)) && ui.input(|input| input.pointer.primary_clicked()) {
+            clicked = Some(index);
+        }
+    }
+
+    ui.painter().line_segment([mixer_center, output_center], egui::Stroke::new(1.5, egui::Color32::from_gray(180)));
+    draw_box(ui.painter(), mixer_center, "+", false);
+    draw_box(ui.painter(), output_center, "Output", false);
+
+    clicked
+}
+
+fn pointer_position(ui: &egui::Ui) -> egui::Pos2 {
+    ui.input(|input| input.pointer.interact_pos()).unwrap_or(egui::pos2(f32::NAN, f32::NAN))
+}
+
+fn line_color(grayed_out: bool) -> egui::Color32 {
+    if grayed_out {
+        egui::Color32::from_gray(60)
+    } else {
+        egui::Color32::from_gray(180)
+    }
+}
+
+fn draw_box(painter: &egui::Painter, center: egui::Pos2, label: &str, grayed_out: bool) -> egui::Rect {
+    let rect = egui::Rect::from_center_size(center, BOX_SIZE);
+    let (fill, text_color) = if grayed_out {
+        (egui::Color32::from_gray(35), egui::Color32::from_gray(120))
+    } else {
+        (egui::Color32::from_gray(55), egui::Color32::WHITE)
+    };
+    painter.rect_filled(rect, 4.0, fill);
+    painter.rect_stroke(rect, 4.0, egui::Stroke::new(1.0, egui::Color32::from_gray(100)));
+    painter.text(center, egui::Align2::CENTER_CENTER, label, egui::FontId::proportional(12.0), text_color);
+    rect
+}
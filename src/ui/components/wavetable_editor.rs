@@ -0,0 +1,158 @@
+use crate::core::synth::samples::CustomWavetable;
+
+/// How mouse movement over the canvas is turned into drawn samples.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DrawMode {
+    /// Every dragged-over point is written directly.
+    #[default]
+    Pencil,
+    /// Only the press and release points are written; everything between
+    /// is filled by linear interpolation once the drag ends.
+    Line,
+}
+
+/// A freehand single-cycle waveform sketchpad. Holds its own fixed-resolution
+/// sample buffer so the canvas keeps state across frames; call `take_wavetable`
+/// to turn the current drawing into a `CustomWavetable`.
+pub struct WavetableEditor {
+    pub buffer: Vec<f32>,
+    pub mode: DrawMode,
+    drag_start: Option<(usize, f32)>,
+}
+
+impl Default for WavetableEditor {
+    fn default() -> Self {
+        Self {
+            buffer: vec![0.0; 1024],
+            mode: DrawMode::default(),
+            drag_start: None,
+        }
+    }
+}
+
+impl WavetableEditor {
+    /// Applies a small Gaussian blur (sigma derived from a 5-tap kernel) to
+    /// smooth out jagged pencil strokes.
+    pub fn smooth(&mut self) {
+        const KERNEL: [f32; 5] = [0.06136, 0.24477, 0.38774, 0.24477, 0.06136];
+        let source = self.buffer.clone();
+        let len = source.len();
+        for (i, sample) in self.buffer.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            for (offset, weight) in KERNEL.iter().enumerate() {
+                let index = (i as isize + offset as isize - 2).rem_euclid(len as isize) as usize;
+                sum += source[index] * weight;
+            }
+            *sample = sum;
+        }
+    }
+
+    /// Scales the buffer so its peak absolute amplitude is `1.0`.
+    pub fn normalize(&mut self) {
+        let peak = self.buffer.iter().fold(0.0f32, |max, sample| max.max(sample.abs()));
+        if peak <= f32::EPSILON {
+            return;
+        }
+        let gain = 1.0 / peak;
+        for sample in self.buffer.iter_mut() {
+            *sample *= gain;
+        }
+    }
+
+    /// Builds a one-frame `CustomWavetable` from the current drawing.
+    pub fn take_wavetable(&self, name: impl Into<String>) -> CustomWavetable {
+        CustomWavetable {
+            name: name.into(),
+            sample_rate: 44_100,
+            frames: vec![self.buffer.clone()],
+            start_position: 0.0,
+            playback_mode: Default::default(),
+        }
+    }
+
+    /// Draws the canvas, drawing tools, and action buttons. Returns `Some`
+    /// with a freshly-built `CustomWavetable` the frame "Add to Synth" is
+    /// clicked.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<CustomWavetable> {
+        ui.heading("Wavetable Editor");
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.mode, DrawMode::Pencil, "Pencil");
+            ui.selectable_value(&mut self.mode, DrawMode::Line, "Line");
+        });
+
+        let size = egui::vec2(ui.available_width().min(512.0), 150.0);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+        ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+        ui.painter().hline(
+            rect.min.x..=rect.max.x,
+            rect.center().y,
+            egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+        );
+
+        if let Some(pointer) = response.interact_pointer_pos() {
+            let fraction = ((pointer.x - rect.min.x) / rect.width()).clamp(0.0, 1.0);
+            let index = ((fraction * self.buffer.len() as f32) as usize).min(self.buffer.len() - 1);
+            let amplitude = (-(pointer.y - rect.center().y) / (rect.height() * 0.5)).clamp(-1.0, 1.0);
+
+            match self.mode {
+                DrawMode::Pencil => self.buffer[index] = amplitude,
+                DrawMode::Line => {
+                    if response.drag_started() {
+                        self.drag_start = Some((index, amplitude));
+                    }
+                    if let Some((start_index, start_amplitude)) = self.drag_start {
+                        if response.drag_stopped() {
+                            interpolate_line(&mut self.buffer, start_index, start_amplitude, index, amplitude);
+                            self.drag_start = None;
+                        }
+                    }
+                }
+            }
+        }
+
+        let points: Vec<egui::Pos2> = self
+            .buffer
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                let x = rect.min.x + rect.width() * (i as f32 / (self.buffer.len() - 1).max(1) as f32);
+                let y = rect.center().y - sample * rect.height() * 0.5;
+                egui::pos2(x, y)
+            })
+            .collect();
+        ui.painter()
+            .add(egui::Shape::line(points, egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 200, 100))));
+
+        let mut new_wavetable = None;
+        ui.horizontal(|ui| {
+            if ui.button("Smooth").clicked() {
+                self.smooth();
+            }
+            if ui.button("Normalize").clicked() {
+                self.normalize();
+            }
+            if ui.button("Add to Synth").clicked() {
+                new_wavetable = Some(self.take_wavetable("Drawn Wavetable"));
+            }
+        });
+
+        new_wavetable
+    }
+}
+
+/// Linearly interpolates the buffer between two drawn endpoints, so a "Line"
+/// stroke fills in every sample the mouse skipped over between press and
+/// release.
+fn interpolate_line(buffer: &mut [f32], start_index: usize, start_amplitude: f32, end_index: usize, end_amplitude: f32) {
+    let (low, high, low_amp, high_amp) = if start_index <= end_index {
+        (start_index, end_index, start_amplitude, end_amplitude)
+    } else {
+        (end_index, start_index, end_amplitude, start_amplitude)
+    };
+    let span = (high - low).max(1) as f32;
+    for (index, sample) in buffer.iter_mut().enumerate().take(high + 1).skip(low) {
+        let fraction = (index - low) as f32 / span;
+        *sample = low_amp + (high_amp - low_amp) * fraction;
+    }
+}
@@ -0,0 +1,64 @@
+use crate::app::SynthApp;
+use crate::ui::components::level_meter;
+
+const BUFFER_SIZE_OPTIONS: [u32; 6] = [64, 128, 256, 512, 1024, 2048];
+
+/// Draws the audio settings panel: buffer size selection, live latency
+/// display, and an "Apply" button that hot-reloads the stream.
+pub fn show(ui: &mut egui::Ui, app: &mut SynthApp) {
+    ui.heading("Audio Settings");
+
+    let mut selected = app.settings.buffer_size_frames.unwrap_or(BUFFER_SIZE_OPTIONS[3]);
+    egui::ComboBox::from_label("Buffer size")
+        .selected_text(format!("{selected}"))
+        .show_ui(ui, |ui| {
+            for option in BUFFER_SIZE_OPTIONS {
+                ui.selectable_value(&mut selected, option, format!("{option}"));
+            }
+        });
+
+    let latency_ms = (selected as f32 / app.sample_rate) * 1000.0;
+    ui.label(format!("Latency: {latency_ms:.1} ms"));
+
+    if ui.button("Apply").clicked() {
+        app.settings.buffer_size_frames = Some(selected);
+        app.change_audio_devices();
+    }
+
+    if let Some(warning) = &app.buffer_size_warning {
+        ui.colored_label(egui::Color32::from_rgb(220, 160, 0), warning);
+    }
+
+    ui.separator();
+    ui.label("Input Device");
+
+    let mut input_capture_enabled = app.settings.input_capture_enabled;
+    if ui.checkbox(&mut input_capture_enabled, "Capture external input (default device)").changed() {
+        app.set_input_capture_enabled(input_capture_enabled);
+    }
+
+    ui.add_enabled_ui(input_capture_enabled, |ui| {
+        let mut input_mix = app.settings.input_mix;
+        if ui.add(egui::Slider::new(&mut input_mix, 0.0..=2.0).text("Input gain")).changed() {
+            app.set_input_mix(input_mix);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Input level:");
+            level_meter::show(ui, app.input_peak_level(), false);
+        });
+    });
+
+    ui.separator();
+    ui.checkbox(&mut app.auto_dc_removal, "Auto-remove DC offset from loaded samples");
+
+    ui.separator();
+    ui.label("Performance");
+    if ui.button("Run Benchmark").clicked() {
+        app.run_benchmark();
+    }
+    ui.label("Measures Synth::render_block throughput under a few patch configurations. Takes several seconds and freezes the UI while it runs.");
+    if let Some(report) = &app.benchmark_report {
+        ui.monospace(report);
+    }
+}
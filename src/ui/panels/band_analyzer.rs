@@ -0,0 +1,135 @@
+use crate::app::SynthApp;
+use crate::core::visualization::{ThirdOctaveAnalyzer, THIRD_OCTAVE_CENTERS_HZ};
+use std::time::Instant;
+
+const FFT_SIZE: usize = 2048;
+const MIN_DBFS: f32 = -80.0;
+const MAX_DBFS: f32 = 0.0;
+/// How long a band's peak indicator holds at its loudest reading before it
+/// starts falling.
+const PEAK_HOLD_SECONDS: f32 = 1.0;
+/// How fast a held peak falls once `PEAK_HOLD_SECONDS` has elapsed.
+const PEAK_FALL_DB_PER_SECOND: f32 = 20.0;
+
+/// One band's peak-hold state: the dB value it peaked at, and when.
+/// `BandAnalyzerDisplay::show` derives the currently-displayed peak from
+/// this each frame rather than mutating it on every call, so the fall rate
+/// stays independent of how often `show` is called.
+struct BandPeak {
+    value_db: f32,
+    set_at: Instant,
+}
+
+/// A classic 31-band third-octave graphic EQ display, grouping
+/// `Analyzer::compute_fft`'s raw spectrum into `ThirdOctaveAnalyzer`'s
+/// standard bands with per-band peak-hold indicators.
+pub struct BandAnalyzerDisplay {
+    peaks: [BandPeak; 31],
+}
+
+impl Default for BandAnalyzerDisplay {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            peaks: std::array::from_fn(|_| BandPeak { value_db: MIN_DBFS, set_at: now }),
+        }
+    }
+}
+
+impl BandAnalyzerDisplay {
+    /// Computes a fresh set of band levels from `app`'s analyzer and draws
+    /// 31 vertical bars (X: band center frequency, Y: -80..0 dBFS), with a
+    /// thin peak-hold line above each bar and bar color sliding from green
+    /// through yellow to red as level increases.
+    pub fn show(&mut self, ui: &mut egui::Ui, app: &mut SynthApp) {
+        app.refresh_analyzer();
+        let fft_output = app.analyzer.compute_fft(app.sample_rate, FFT_SIZE);
+        let bands = ThirdOctaveAnalyzer::compute(&fft_output, app.sample_rate);
+        let now = Instant::now();
+
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(420.0), 160.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(15));
+
+        let band_width = rect.width() / bands.len() as f32;
+        for (index, &magnitude) in bands.iter().enumerate() {
+            let db = magnitude_to_dbfs(magnitude);
+            let held_db = self.peaks[index].held_value(now);
+
+            if db >= held_db {
+                self.peaks[index].value_db = db;
+                self.peaks[index].set_at = now;
+            }
+            let displayed_peak_db = self.peaks[index].held_value(now).max(db);
+
+            let x = rect.min.x + index as f32 * band_width;
+            let bar_rect = egui::Rect::from_min_size(egui::pos2(x, rect.min.y), egui::vec2(band_width - 1.0, rect.height()));
+            let level = normalize_dbfs(db);
+            let bar_height = rect.height() * level;
+            let bar = egui::Rect::from_min_size(egui::pos2(x, rect.max.y - bar_height), egui::vec2(band_width - 1.0, bar_height));
+            ui.painter().rect_filled(bar, 0.0, level_to_color(level));
+
+            let peak_y = rect.max.y - rect.height() * normalize_dbfs(displayed_peak_db);
+            ui.painter().hline(bar_rect.x_range(), peak_y, egui::Stroke::new(1.5, egui::Color32::WHITE));
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for center_hz in THIRD_OCTAVE_CENTERS_HZ {
+                ui.label(format_center_hz(center_hz));
+            }
+        });
+    }
+}
+
+impl BandPeak {
+    /// The dB value this band's peak indicator should currently show:
+    /// `value_db` until `PEAK_HOLD_SECONDS` elapses, then falling at
+    /// `PEAK_FALL_DB_PER_SECOND`, floored at `MIN_DBFS`.
+    fn held_value(&self, now: Instant) -> f32 {
+        let elapsed = now.duration_since(self.set_at).as_secs_f32();
+        if elapsed <= PEAK_HOLD_SECONDS {
+            self.value_db
+        } else {
+            (self.value_db - PEAK_FALL_DB_PER_SECOND * (elapsed - PEAK_HOLD_SECONDS)).max(MIN_DBFS)
+        }
+    }
+}
+
+fn magnitude_to_dbfs(magnitude: f32) -> f32 {
+    if magnitude <= f32::EPSILON {
+        MIN_DBFS
+    } else {
+        (20.0 * magnitude.log10()).clamp(MIN_DBFS, MAX_DBFS)
+    }
+}
+
+fn normalize_dbfs(db: f32) -> f32 {
+    ((db - MIN_DBFS) / (MAX_DBFS - MIN_DBFS)).clamp(0.0, 1.0)
+}
+
+/// Green through yellow to red as `level` (0.0-1.0) increases.
+fn level_to_color(level: f32) -> egui::Color32 {
+    if level < 0.6 {
+        let t = level / 0.6;
+        lerp_color(egui::Color32::from_rgb(20, 160, 60), egui::Color32::from_rgb(230, 210, 30), t)
+    } else {
+        let t = (level - 0.6) / 0.4;
+        lerp_color(egui::Color32::from_rgb(230, 210, 30), egui::Color32::from_rgb(230, 40, 30), t)
+    }
+}
+
+fn lerp_color(lo: egui::Color32, hi: egui::Color32, t: f32) -> egui::Color32 {
+    let t = t.clamp(0.0, 1.0);
+    egui::Color32::from_rgb(
+        (lo.r() as f32 + (hi.r() as f32 - lo.r() as f32) * t) as u8,
+        (lo.g() as f32 + (hi.g() as f32 - lo.g() as f32) * t) as u8,
+        (lo.b() as f32 + (hi.b() as f32 - lo.b() as f32) * t) as u8,
+    )
+}
+
+fn format_center_hz(center_hz: f32) -> String {
+    if center_hz >= 1000.0 {
+        format!("{:.1}k", center_hz / 1000.0)
+    } else {
+        format!("{center_hz:.0}")
+    }
+}
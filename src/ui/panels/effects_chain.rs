@@ -0,0 +1,247 @@
+use crate::core::effects::convolution_reverb::LONG_IR_WARNING_SECONDS;
+use crate::core::effects::{Compressor, ConvolutionReverb, DetectionMode, EffectType, EffectsChain, EqFilterType, ParametricEq, Phaser};
+
+/// Persistent UI state for the effects chain panel: just the IR file path
+/// text box, since everything else reads straight from `EffectsChain`.
+#[derive(Default)]
+pub struct EffectsChainPanel {
+    pub ir_path_input: String,
+}
+
+impl EffectsChainPanel {
+    /// Draws the effects chain, same as the free-standing `show`, but with
+    /// an IR file loader available to any `ConvolutionReverb` slots.
+    pub fn show(&mut self, ui: &mut egui::Ui, chain: &mut EffectsChain, sample_rate: f32) {
+        show_with_ir_loader(ui, chain, Some((&mut self.ir_path_input, sample_rate)));
+    }
+}
+
+/// Draws the effects chain as a reorderable, drag-and-drop list of strips.
+/// Dragging a strip up or down past a neighbor swaps their order in
+/// `chain.slots`, since processing order changes how the chain sounds.
+pub fn show(ui: &mut egui::Ui, chain: &mut EffectsChain) {
+    show_with_ir_loader(ui, chain, None);
+}
+
+fn show_with_ir_loader(ui: &mut egui::Ui, chain: &mut EffectsChain, ir_loader: Option<(&mut String, f32)>) {
+    let (mut ir_path_input, ir_sample_rate) = match ir_loader {
+        Some((path, sample_rate)) => (Some(path), sample_rate),
+        None => (None, 44_100.0),
+    };
+    ui.heading("Effects Chain");
+
+    ui.checkbox(&mut chain.bypassed, "Bypass (compare wet vs dry)");
+
+    let mut pending_reorder = None;
+    let mut pending_removal = None;
+
+    for (index, slot) in chain.slots.iter_mut().enumerate() {
+        let frame = egui::Frame::group(ui.style());
+        let response = frame.show(ui, |ui| {
+            let drag_handle = ui
+                .horizontal(|ui| {
+                    let drag_handle = ui.label("\u{2630}").interact(egui::Sense::drag());
+                    ui.checkbox(&mut slot.enabled, "");
+                    ui.label(slot.effect.effect_type().label());
+                    ui.add(egui::Slider::new(&mut slot.wet_dry, 0.0..=1.0).text("Wet/Dry"));
+                    if ui.button("Remove").clicked() {
+                        pending_removal = Some(index);
+                    }
+                    drag_handle
+                })
+                .inner;
+
+            if let Some(compressor) = slot.effect.as_any_mut().downcast_mut::<Compressor>() {
+                show_compressor(ui, compressor);
+            }
+            if let Some(phaser) = slot.effect.as_any_mut().downcast_mut::<Phaser>() {
+                show_phaser(ui, phaser);
+            }
+            if let Some(convolution_reverb) = slot.effect.as_any_mut().downcast_mut::<ConvolutionReverb>() {
+                show_convolution_reverb(ui, convolution_reverb, ir_path_input.as_deref_mut(), ir_sample_rate);
+            }
+            if let Some(parametric_eq) = slot.effect.as_any_mut().downcast_mut::<ParametricEq>() {
+                show_parametric_eq(ui, parametric_eq, ir_sample_rate);
+            }
+
+            drag_handle
+        });
+
+        let drag_handle = response.inner;
+        if drag_handle.dragged() {
+            let drag_delta_y = drag_handle.drag_delta().y;
+            let row_height = response.response.rect.height().max(1.0);
+            if drag_delta_y.abs() > row_height * 0.5 {
+                let target = if drag_delta_y > 0.0 { index + 1 } else { index.saturating_sub(1) };
+                pending_reorder = Some((index, target));
+            }
+        }
+    }
+
+    if let Some(index) = pending_removal {
+        chain.remove(index);
+    } else if let Some((from, to)) = pending_reorder {
+        chain.reorder(from, to);
+    }
+
+    ui.menu_button("+ Add Effect", |ui| {
+        for effect_type in [
+            EffectType::Chorus,
+            EffectType::Delay,
+            EffectType::Reverb,
+            EffectType::Distortion,
+            EffectType::Compressor,
+            EffectType::Phaser,
+            EffectType::ConvolutionReverb,
+            EffectType::ParametricEq,
+        ] {
+            if ui.button(effect_type.label()).clicked() {
+                chain.push(effect_type);
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+/// Standard compressor knobs plus a gain reduction meter, shown beneath a
+/// slot's strip when it holds a `Compressor`.
+fn show_compressor(ui: &mut egui::Ui, compressor: &mut Compressor) {
+    ui.add(egui::Slider::new(&mut compressor.threshold_db, -60.0..=0.0).text("Threshold (dB)"));
+    ui.add(egui::Slider::new(&mut compressor.ratio, 1.0..=20.0).text("Ratio"));
+    ui.add(egui::Slider::new(&mut compressor.attack_ms, 0.1..=200.0).text("Attack (ms)"));
+    ui.add(egui::Slider::new(&mut compressor.release_ms, 1.0..=1000.0).text("Release (ms)"));
+    ui.add(egui::Slider::new(&mut compressor.knee_db, 0.0..=24.0).text("Knee (dB)"));
+    ui.add(egui::Slider::new(&mut compressor.makeup_gain_db, 0.0..=24.0).text("Makeup Gain (dB)"));
+
+    ui.horizontal(|ui| {
+        ui.label("Detection:");
+        ui.selectable_value(&mut compressor.detection_mode, DetectionMode::Peak, "Peak");
+        ui.selectable_value(&mut compressor.detection_mode, DetectionMode::Rms, "RMS");
+    });
+
+    let meter_fraction = (-compressor.gain_reduction_db / 24.0).clamp(0.0, 1.0);
+    ui.add(
+        egui::ProgressBar::new(meter_fraction)
+            .text(format!("{:.1} dB GR", -compressor.gain_reduction_db))
+            .desired_width(160.0),
+    );
+}
+
+/// Standard phaser knobs, shown beneath a slot's strip when it holds a
+/// `Phaser`.
+fn show_phaser(ui: &mut egui::Ui, phaser: &mut Phaser) {
+    ui.add(egui::Slider::new(&mut phaser.stages, 4..=16).text("Stages"));
+    ui.add(egui::Slider::new(&mut phaser.center_hz, 100.0..=4_000.0).logarithmic(true).text("Center (Hz)"));
+    ui.add(egui::Slider::new(&mut phaser.rate, 0.05..=10.0).text("Rate (Hz)"));
+    ui.add(egui::Slider::new(&mut phaser.depth, 0.0..=1.0).text("Depth"));
+    ui.add(egui::Slider::new(&mut phaser.feedback, 0.0..=0.95).text("Feedback"));
+    ui.add(egui::Slider::new(&mut phaser.mix, 0.0..=1.0).text("Mix"));
+}
+
+/// Three bands' worth of frequency/gain/Q/type controls, plus a log-spaced
+/// frequency-response overlay, shown beneath a slot's strip when it holds a
+/// `ParametricEq`.
+fn show_parametric_eq(ui: &mut egui::Ui, eq: &mut ParametricEq, sample_rate: f32) {
+    for (index, band) in eq.bands.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("Band {}", index + 1));
+            ui.add(egui::Slider::new(&mut band.frequency, 20.0..=20_000.0).logarithmic(true).text("Freq (Hz)"));
+            ui.add(egui::Slider::new(&mut band.gain_db, -20.0..=20.0).text("Gain (dB)"));
+            ui.add(egui::Slider::new(&mut band.q, 0.1..=10.0).text("Q"));
+            egui::ComboBox::from_id_source(("eq_band_type", index))
+                .selected_text(eq_filter_type_label(band.filter_type))
+                .show_ui(ui, |ui| {
+                    for filter_type in [EqFilterType::Peaking, EqFilterType::LowShelf, EqFilterType::HighShelf, EqFilterType::Notch] {
+                        ui.selectable_value(&mut band.filter_type, filter_type, eq_filter_type_label(filter_type));
+                    }
+                });
+        });
+    }
+
+    let curve = eq.response_curve(20.0, 20_000.0, 128, sample_rate);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(320.0), 80.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+
+    let min_db = -24.0;
+    let max_db = 24.0;
+    let zero_y = rect.max.y - rect.height() * ((0.0 - min_db) / (max_db - min_db));
+    ui.painter().line_segment(
+        [egui::pos2(rect.min.x, zero_y), egui::pos2(rect.max.x, zero_y)],
+        egui::Stroke::new(1.0, egui::Color32::from_gray(70)),
+    );
+
+    let points: Vec<egui::Pos2> = curve
+        .iter()
+        .enumerate()
+        .map(|(i, (_, db))| {
+            let x = rect.min.x + rect.width() * (i as f32 / (curve.len() - 1) as f32);
+            let t = ((db - min_db) / (max_db - min_db)).clamp(0.0, 1.0);
+            let y = rect.max.y - rect.height() * t;
+            egui::pos2(x, y)
+        })
+        .collect();
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.5, egui::Color32::from_rgb(100, 200, 255))));
+}
+
+fn eq_filter_type_label(filter_type: EqFilterType) -> &'static str {
+    match filter_type {
+        EqFilterType::Peaking => "Peaking",
+        EqFilterType::LowShelf => "Low Shelf",
+        EqFilterType::HighShelf => "High Shelf",
+        EqFilterType::Notch => "Notch",
+    }
+}
+
+/// IR file loader (when `ir_path_input` is available, i.e. drawn via
+/// `EffectsChainPanel::show`), pre-delay/trim controls, and an inline IR
+/// waveform plot, shown beneath a slot's strip when it holds a
+/// `ConvolutionReverb`.
+fn show_convolution_reverb(
+    ui: &mut egui::Ui,
+    convolution_reverb: &mut ConvolutionReverb,
+    ir_path_input: Option<&mut String>,
+    sample_rate: f32,
+) {
+    if let Some(ir_path_input) = ir_path_input {
+        ui.horizontal(|ui| {
+            ui.label("IR File:");
+            ui.text_edit_singleline(ir_path_input);
+            if ui.button("Load").clicked() {
+                match ConvolutionReverb::load_ir(std::path::Path::new(ir_path_input.as_str()), sample_rate) {
+                    Ok(loaded) => *convolution_reverb = loaded,
+                    Err(error) => {
+                        ui.colored_label(egui::Color32::RED, format!("IR load failed: {error}"));
+                    }
+                }
+            }
+        });
+    }
+
+    if convolution_reverb.is_long_ir {
+        ui.colored_label(
+            egui::Color32::from_rgb(230, 180, 40),
+            format!("\u{26a0} IR is longer than {LONG_IR_WARNING_SECONDS:.0}s — convolution will be CPU-heavy"),
+        );
+    }
+
+    ui.add(egui::Slider::new(&mut convolution_reverb.pre_delay_samples, 0..=48_000).text("Pre-delay (samples)"));
+    ui.add(egui::Slider::new(&mut convolution_reverb.trim, 0.0..=1.0).text("IR Trim"));
+
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(320.0), 48.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+    let impulse_response = &convolution_reverb.impulse_response;
+    if impulse_response.is_empty() {
+        return;
+    }
+    let plot_points = rect.width().max(1.0) as usize;
+    let points: Vec<egui::Pos2> = (0..plot_points)
+        .map(|i| {
+            let sample_index = i * impulse_response.len() / plot_points;
+            let sample = impulse_response[sample_index.min(impulse_response.len() - 1)];
+            let x = rect.min.x + i as f32;
+            let y = rect.center().y - sample.clamp(-1.0, 1.0) * rect.height() * 0.5;
+            egui::pos2(x, y)
+        })
+        .collect();
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 200, 100))));
+}
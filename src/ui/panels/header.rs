@@ -0,0 +1,49 @@
+use crate::app::SynthApp;
+
+/// Draws the top header bar, including the CPU load meter: green below
+/// 50%, yellow 50-80%, red above 80%.
+pub fn show(ui: &mut egui::Ui, app: &mut SynthApp) {
+    let load = app.cpu_load();
+    let color = if load > 0.8 {
+        egui::Color32::from_rgb(220, 50, 50)
+    } else if load > 0.5 {
+        egui::Color32::from_rgb(220, 180, 0)
+    } else {
+        egui::Color32::from_rgb(60, 180, 60)
+    };
+
+    ui.horizontal(|ui| {
+        ui.label(format!("CPU: {:.0}%", load * 100.0));
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(80.0, 10.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(40));
+        let filled = rect.with_max_x(rect.min.x + rect.width() * load.clamp(0.0, 1.0));
+        ui.painter().rect_filled(filled, 2.0, color);
+
+        ui.label("Master:");
+        if crate::ui::components::level_meter::show(ui, app.master_peak_level(), app.master_clipped()) {
+            app.reset_master_clip();
+        }
+
+        let mut auto_limiter_enabled = app.current_auto_limiter_enabled();
+        if ui.checkbox(&mut auto_limiter_enabled, "Limiter").changed() {
+            app.set_auto_limiter_enabled(auto_limiter_enabled);
+        }
+    });
+
+    while let Ok(message) = app.message_receiver.try_recv() {
+        match message {
+            crate::app::SynthMessage::AudioOverload => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 50, 50),
+                    "Audio overload: reduce polyphony or effect complexity",
+                );
+            }
+            crate::app::SynthMessage::ProgramChange(program) => {
+                app.handle_program_change(program);
+            }
+            crate::app::SynthMessage::SysEx(data) => {
+                app.apply_sysex_message(&data);
+            }
+        }
+    }
+}
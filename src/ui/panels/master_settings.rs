@@ -0,0 +1,178 @@
+use crate::app::SynthApp;
+use crate::core::oscillator::{HarmonicPartial, OscillatorCombinationMode};
+use crate::core::synth::RetriggerMode;
+use crate::core::theory::{Scale, ScaleType};
+
+const ROOT_NOTE_NAMES: [&str; 12] =
+    ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+const SCALE_TYPES: [ScaleType; 9] = [
+    ScaleType::Major,
+    ScaleType::Minor,
+    ScaleType::Pentatonic,
+    ScaleType::Blues,
+    ScaleType::Dorian,
+    ScaleType::Phrygian,
+    ScaleType::Lydian,
+    ScaleType::Mixolydian,
+    ScaleType::Locrian,
+];
+
+fn combination_mode_label(mode: &OscillatorCombinationMode) -> &'static str {
+    match mode {
+        OscillatorCombinationMode::Mix => "Mix",
+        OscillatorCombinationMode::Additive(_) => "Additive",
+    }
+}
+
+fn label(mode: RetriggerMode) -> &'static str {
+    match mode {
+        RetriggerMode::Reset => "Reset",
+        RetriggerMode::NoteOff => "Note Off",
+        RetriggerMode::Gate => "Gate",
+    }
+}
+
+fn scale_type_label(scale_type: &ScaleType) -> &'static str {
+    match scale_type {
+        ScaleType::Major => "Major",
+        ScaleType::Minor => "Minor",
+        ScaleType::Pentatonic => "Pentatonic",
+        ScaleType::Blues => "Blues",
+        ScaleType::Dorian => "Dorian",
+        ScaleType::Phrygian => "Phrygian",
+        ScaleType::Lydian => "Lydian",
+        ScaleType::Mixolydian => "Mixolydian",
+        ScaleType::Locrian => "Locrian",
+        ScaleType::Custom(_) => "Custom",
+    }
+}
+
+/// Draws the master settings panel: retrigger behavior and scale-lock
+/// quantization, the natural home for future synth-wide (as opposed to
+/// per-oscillator) options.
+pub fn show(ui: &mut egui::Ui, app: &mut SynthApp) {
+    ui.heading("Master Settings");
+
+    ui.label("Oscillator Routing");
+    let current_mode = app.current_combination_mode().clone();
+    let mut selected_mode = current_mode.clone();
+    egui::ComboBox::from_label("Combination mode")
+        .selected_text(combination_mode_label(&selected_mode))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut selected_mode, OscillatorCombinationMode::Mix, "Mix");
+            ui.selectable_value(
+                &mut selected_mode,
+                OscillatorCombinationMode::Additive(vec![HarmonicPartial { harmonic_number: 1, amplitude: 1.0 }]),
+                "Additive",
+            );
+        });
+    if selected_mode != current_mode {
+        app.set_combination_mode(selected_mode);
+    }
+    crate::ui::components::routing_diagram::show(ui, app.current_combination_mode(), app.current_oscillator_templates());
+
+    ui.separator();
+    let mut selected = app.current_retrigger_mode();
+    egui::ComboBox::from_label("Retrigger mode")
+        .selected_text(label(selected))
+        .show_ui(ui, |ui| {
+            for mode in [RetriggerMode::Reset, RetriggerMode::NoteOff, RetriggerMode::Gate] {
+                ui.selectable_value(&mut selected, mode, label(mode));
+            }
+        });
+    if selected != app.current_retrigger_mode() {
+        app.set_retrigger_mode(selected);
+    }
+
+    ui.separator();
+    ui.label("Scale Lock");
+
+    let mut enabled = app.current_scale_quantize().is_some();
+    let mut root = app.current_scale_quantize().map(|scale| scale.root).unwrap_or(0);
+    let mut scale_type = app
+        .current_scale_quantize()
+        .map(|scale| scale.scale_type.clone())
+        .unwrap_or(ScaleType::Major);
+
+    let mut changed = ui.checkbox(&mut enabled, "Enabled").changed();
+
+    ui.add_enabled_ui(enabled, |ui| {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Root")
+                .selected_text(ROOT_NOTE_NAMES[root as usize])
+                .show_ui(ui, |ui| {
+                    for (index, name) in ROOT_NOTE_NAMES.iter().enumerate() {
+                        changed |= ui.selectable_value(&mut root, index as u8, *name).changed();
+                    }
+                });
+
+            egui::ComboBox::from_label("Scale")
+                .selected_text(scale_type_label(&scale_type))
+                .show_ui(ui, |ui| {
+                    for candidate in &SCALE_TYPES {
+                        let response = ui.selectable_label(scale_type == *candidate, scale_type_label(candidate));
+                        if response.clicked() {
+                            scale_type = candidate.clone();
+                            changed = true;
+                        }
+                    }
+                });
+        });
+    });
+
+    if changed {
+        app.set_scale_quantize(enabled.then(|| Scale::new(root, scale_type)));
+    }
+
+    ui.separator();
+    ui.collapsing("Velocity", |ui| {
+        let mut velocity_scaling = app.current_velocity_scaling();
+        let mut changed = false;
+
+        changed |= ui
+            .add(egui::Slider::new(&mut velocity_scaling.velocity_to_attack_scale, 0.0..=1.0).text("Velocity -> Attack"))
+            .changed();
+        changed |= ui
+            .add(egui::Slider::new(&mut velocity_scaling.velocity_to_release_scale, 0.0..=1.0).text("Velocity -> Release"))
+            .changed();
+
+        if changed {
+            app.set_velocity_scaling(velocity_scaling);
+        }
+    });
+
+    ui.separator();
+    ui.label("Macros");
+    ui.horizontal(|ui| {
+        for index in 0..app.current_macros().len() {
+            let mut value = app.current_macros()[index].value;
+            let route_count = app.current_macros()[index].routes.len();
+            ui.vertical(|ui| {
+                ui.label(app.current_macros()[index].name.as_str());
+                if ui.add(egui::Slider::new(&mut value, 0.0..=1.0).vertical()).changed() {
+                    app.set_macro_value(index, value);
+                }
+                ui.label(format!("{route_count} route(s)"));
+            });
+        }
+    });
+
+    ui.separator();
+    ui.label("Pitch Bend");
+    let mut pitch_bend = app.current_pitch_bend();
+    ui.horizontal(|ui| {
+        if ui.add(egui::Slider::new(&mut pitch_bend, -1.0..=1.0).text("Wheel")).changed() {
+            app.set_pitch_bend(pitch_bend);
+        }
+        if ui.button("Center").clicked() {
+            app.set_pitch_bend(0.0);
+        }
+    });
+
+    ui.separator();
+    if ui.button("\u{1f6a8} Panic Reset").clicked() {
+        app.panic_reset();
+    }
+    ui.label("Hard reset: drops every voice and rebuilds effect DSP state, without touching any parameters.");
+}
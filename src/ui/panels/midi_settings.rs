@@ -0,0 +1,270 @@
+use crate::app::SynthApp;
+use crate::core::midi::{MidiControlTarget, MidiMappingEntry};
+use crate::core::synth::Synth;
+use crate::core::theory::chord_recognition::ChordRecognizer;
+
+/// The white keys of the home row, in order, mapped to ascending scale
+/// degrees starting at C. There is no computer-keyboard-to-note input path
+/// in this app yet, so this table is reference-only (it doesn't itself
+/// drive `note_on`), matching the convention players learn on a physical
+/// MIDI keyboard layout app (A=C, S=D, D=E, F=F, G=G, H=A, J=B, K=C).
+const QWERTY_WHITE_KEYS: [(&str, i32); 8] =
+    [("A", 0), ("S", 2), ("D", 4), ("F", 5), ("G", 7), ("H", 9), ("J", 11), ("K", 12)];
+
+const NOTE_NAMES: [&str; 12] =
+    ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+/// Formats a MIDI note number as e.g. `"C4"`, using the common convention
+/// that MIDI note 60 is C4.
+fn note_name(note: i32) -> String {
+    let octave = note.div_euclid(12) - 1;
+    let name = NOTE_NAMES[note.rem_euclid(12) as usize];
+    format!("{name}{octave}")
+}
+
+/// All targets a MIDI CC can be learned to, for the selector below. Kept as
+/// a flat list rather than deriving it from live oscillator/effect counts
+/// so the selector still works before any oscillators or effects exist.
+fn learnable_targets() -> Vec<MidiControlTarget> {
+    let mut targets = vec![MidiControlTarget::MasterVolume];
+    for osc in 0..4 {
+        targets.push(MidiControlTarget::OscillatorVolume(osc));
+        targets.push(MidiControlTarget::OscillatorPan(osc));
+        targets.push(MidiControlTarget::OscillatorDetune(osc));
+        targets.push(MidiControlTarget::FilterCutoff(osc));
+        targets.push(MidiControlTarget::FilterResonance(osc));
+        targets.push(MidiControlTarget::WavetablePosition(osc));
+    }
+    for slot in 0..4 {
+        targets.push(MidiControlTarget::EffectWetDry(slot));
+    }
+    for index in 0..8 {
+        targets.push(MidiControlTarget::Macro(index));
+    }
+    targets
+}
+
+/// Draws the MIDI settings panel, including a live activity log of
+/// recently received messages.
+pub fn show(ui: &mut egui::Ui, app: &mut SynthApp) {
+    ui.heading("MIDI Settings");
+
+    ui.separator();
+    ui.heading("Live Input");
+    match &app.midi_input_port_name {
+        Some(name) => {
+            ui.label(format!("Connected: {name}"));
+            if ui.button("Disconnect").clicked() {
+                app.disconnect_midi_input();
+            }
+        }
+        None => {
+            ui.label("Not connected.");
+            for port in SynthApp::available_midi_input_ports() {
+                if ui.button(format!("Connect to {port}")).clicked() {
+                    app.connect_midi_input(&port);
+                }
+            }
+        }
+    }
+    if let Some(error) = &app.midi_input_error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+    if let Some((channel, controller, value)) = app.last_unmapped_cc {
+        ui.label(format!("Last unmapped CC: ch{channel} CC{controller} = {value:.3}"));
+    }
+    ui.checkbox(&mut app.mpe_enabled, "MPE (per-note pitch bend and pressure)");
+
+    ui.separator();
+    ui.heading("SysEx");
+    ui.label("Bulk preset dump over System Exclusive, for compatible hardware.");
+
+    if ui.button("Send Current Preset").clicked() {
+        let dump = app.encode_current_preset_sysex();
+        app.sysex_import_input = dump.iter().map(|byte| format!("{byte:02X}")).collect::<Vec<_>>().join(" ");
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Dump (hex):");
+        ui.text_edit_singleline(&mut app.sysex_import_input);
+    });
+    if ui.button("Load Preset From Dump").clicked() {
+        let bytes: Option<Vec<u8>> = app
+            .sysex_import_input
+            .split_whitespace()
+            .map(|token| u8::from_str_radix(token, 16).ok())
+            .collect();
+        if let Some(bytes) = bytes {
+            app.apply_sysex_message(&bytes);
+        }
+    }
+
+    ui.separator();
+    ui.heading("MIDI Learn");
+    ui.label("Bind a CC number to a synth parameter; the next matching CC message will control it.");
+
+    ui.horizontal(|ui| {
+        ui.label("Channel");
+        ui.add(egui::DragValue::new(&mut app.midi_learn_channel).clamp_range(0..=15));
+        ui.label("Controller");
+        ui.add(egui::DragValue::new(&mut app.midi_learn_controller).clamp_range(0..=127));
+        egui::ComboBox::from_id_source("midi_learn_target")
+            .selected_text(app.midi_learn_target.label())
+            .show_ui(ui, |ui| {
+                for target in learnable_targets() {
+                    ui.selectable_value(&mut app.midi_learn_target, target, target.label());
+                }
+            });
+        if ui.button("Bind").clicked() {
+            let channel = app.midi_learn_channel;
+            let controller = app.midi_learn_controller;
+            let target = app.midi_learn_target;
+            app.midi_system.lock().unwrap().learn_map.bind(channel, controller, target);
+        }
+    });
+
+    let bindings: Vec<((u8, u8), MidiMappingEntry)> =
+        app.midi_system.lock().unwrap().learn_map.bindings().map(|(key, entry)| (*key, *entry)).collect();
+    for ((channel, controller), mut entry) in bindings {
+        ui.horizontal(|ui| {
+            ui.label(format!("ch{channel} CC{controller} -> {}", entry.target.label()));
+            let mut changed = false;
+            changed |= ui.add(egui::DragValue::new(&mut entry.in_min).clamp_range(0..=127).prefix("in ")).changed();
+            changed |= ui.add(egui::DragValue::new(&mut entry.in_max).clamp_range(0..=127).prefix("-")).changed();
+            changed |= ui.add(egui::DragValue::new(&mut entry.out_min).speed(0.01).prefix("out ")).changed();
+            changed |= ui.add(egui::DragValue::new(&mut entry.out_max).speed(0.01).prefix("-")).changed();
+            changed |= ui.checkbox(&mut entry.invert, "Invert").changed();
+            if changed {
+                if let Some(mapping) = app.midi_system.lock().unwrap().learn_map.mapping_mut(channel, controller) {
+                    *mapping = entry;
+                }
+            }
+            if ui.button("Unbind").clicked() {
+                app.midi_system.lock().unwrap().learn_map.unbind(channel, controller);
+            }
+        });
+    }
+
+    if ui.button("Panic (All Notes Off)").clicked() {
+        app.panic_all_notes_off();
+    }
+
+    ui.separator();
+    ui.heading("Keyboard Reference");
+    ui.label("Which MIDI note each QWERTY key corresponds to, for players without a MIDI keyboard.");
+    ui.horizontal(|ui| {
+        ui.label(format!("Octave offset: {}", app.keyboard_octave_offset));
+        if ui.button("-").clicked() {
+            app.keyboard_octave_offset -= 1;
+        }
+        if ui.button("+").clicked() {
+            app.keyboard_octave_offset += 1;
+        }
+    });
+    egui::Grid::new("midi_keyboard_reference").striped(true).show(ui, |ui| {
+        ui.strong("Key");
+        ui.strong("MIDI Note");
+        ui.end_row();
+        for (key, degree) in QWERTY_WHITE_KEYS {
+            let note = 60 + degree + app.keyboard_octave_offset as i32 * 12;
+            ui.label(key);
+            ui.label(note_name(note));
+            ui.end_row();
+        }
+    });
+
+    ui.separator();
+    ui.heading("Activity Log");
+
+    let mut log = app.midi_activity_log.lock().unwrap();
+    let mut paused = log.paused();
+    ui.horizontal(|ui| {
+        if ui.checkbox(&mut paused, "Pause").changed() {
+            log.set_paused(paused);
+        }
+        if ui.button("Clear").clicked() {
+            log.clear();
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut app.midi_log_filter.show_note_on, "Note On");
+        ui.checkbox(&mut app.midi_log_filter.show_note_off, "Note Off");
+        ui.checkbox(&mut app.midi_log_filter.show_control_change, "CC");
+        ui.checkbox(&mut app.midi_log_filter.show_pitch_bend, "Pitch Bend");
+        ui.checkbox(&mut app.midi_log_filter.show_polyphonic_aftertouch, "Poly AT");
+    });
+
+    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+        for entry in log.entries().filter(|entry| app.midi_log_filter.allows(entry.message_type)) {
+            let elapsed_ms = entry.timestamp.elapsed().as_millis();
+            ui.monospace(format!(
+                "{}ms ago  {:<10} ch{:<2} {:02X} {:02X} {:02X}",
+                elapsed_ms,
+                entry.message_type.label(),
+                entry.channel,
+                entry.data[0],
+                entry.data[1],
+                entry.data[2],
+            ));
+        }
+    });
+    drop(log);
+
+    ui.separator();
+    ui.heading("Program Change Map");
+    ui.label("Assigns MIDI Program Change numbers (0-127) to presets.");
+
+    if ui.button("Auto-assign").clicked() {
+        app.auto_assign_program_changes();
+    }
+
+    egui::ScrollArea::vertical().max_height(200.0).id_source("program_change_scroll").show(ui, |ui| {
+        for program in 0u8..=127 {
+            ui.horizontal(|ui| {
+                ui.label(format!("{:>3}", program));
+                let mut assigned = app.settings.program_change_map.get(&program).cloned();
+                egui::ComboBox::from_id_source(("program_change", program))
+                    .selected_text(assigned.clone().unwrap_or_else(|| "-".to_string()))
+                    .show_ui(ui, |ui| {
+                        if ui.selectable_label(assigned.is_none(), "-").clicked() {
+                            assigned = None;
+                        }
+                        for preset in &app.presets {
+                            if ui.selectable_label(assigned.as_deref() == Some(preset.name.as_str()), &preset.name).clicked() {
+                                assigned = Some(preset.name.clone());
+                            }
+                        }
+                    });
+                match assigned {
+                    Some(name) => {
+                        app.settings.program_change_map.insert(program, name);
+                    }
+                    None => {
+                        app.settings.program_change_map.remove(&program);
+                    }
+                }
+            });
+        }
+    });
+}
+
+/// Shows the chord currently being held, identified from `synth`'s active
+/// voices via `ChordRecognizer`. Takes `&Synth` directly rather than
+/// `&SynthApp`, matching `voice_status::show`, since `SynthApp` has no
+/// readback of currently-held notes of its own.
+pub fn show_chord_monitor(ui: &mut egui::Ui, synth: &Synth) {
+    ui.heading("Chord Monitor");
+    let notes: Vec<u8> = synth.voices.keys().copied().collect();
+    match ChordRecognizer::identify(&notes) {
+        Some(chord) => {
+            ui.label(format!("{chord}"));
+        }
+        None if notes.is_empty() => {
+            ui.label("(no notes held)");
+        }
+        None => {
+            ui.label("(unrecognized)");
+        }
+    }
+}
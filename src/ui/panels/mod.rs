@@ -0,0 +1,15 @@
+pub mod audio_settings;
+pub mod band_analyzer;
+pub mod effects_chain;
+pub mod header;
+pub mod master_settings;
+pub mod midi_settings;
+pub mod oscillator_panel;
+pub mod oscilloscope;
+pub mod preset_browser;
+pub mod sequencer;
+pub mod stereo_scope;
+pub mod tuner;
+pub mod voice_status;
+pub mod waterfall;
+pub mod wavetable_management;
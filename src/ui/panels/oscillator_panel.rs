@@ -0,0 +1,146 @@
+use crate::core::macros::ModTarget;
+use crate::core::oscillator::{Oscillator, StereoDelayMod};
+
+/// How many macro knobs `assign_to_macro_menu` offers — mirrors
+/// `Synth::macros`' fixed size.
+const MACRO_COUNT: usize = 8;
+
+/// What the user requested via this panel's clipboard/swap/macro-assign
+/// buttons. This panel only ever borrows one oscillator at a time, so it
+/// can't apply an action that touches another oscillator (or `SynthApp`'s
+/// macros) itself — the caller applies it against its own `Vec<Oscillator>`
+/// (e.g. `SynthApp::copy_oscillator`/`paste_oscillator`/`swap_oscillators`)
+/// or `SynthApp::assign_macro_route`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OscillatorPanelAction {
+    None,
+    ClipReset,
+    Copy,
+    Paste,
+    SwapWith(usize),
+    /// Right-click "Assign to Macro N" on one of this oscillator's
+    /// sliders: the chosen macro index and the parameter it should drive.
+    AssignToMacro(usize, ModTarget),
+}
+
+/// Right-click menu offering to route `target` through one of the eight
+/// macro knobs, attached to a parameter slider's response.
+fn assign_to_macro_menu(response: &egui::Response, action: &mut OscillatorPanelAction, target: ModTarget) {
+    response.context_menu(|ui| {
+        for macro_index in 0..MACRO_COUNT {
+            if ui.button(format!("Assign to Macro {}", macro_index + 1)).clicked() {
+                *action = OscillatorPanelAction::AssignToMacro(macro_index, target);
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+/// Draws the controls for a single oscillator, including its filter
+/// section with a graphical frequency response display and a peak meter.
+/// `peak`/`clipped` come from `SynthApp::osc_peak_level`/`osc_clipped` for
+/// this oscillator's template index. `index`/`oscillator_count` place this
+/// panel among its siblings for the "Swap with" menu, and `clipboard`
+/// reflects whether `SynthApp::oscillator_clipboard` currently holds a
+/// copied oscillator (to enable/disable "Paste settings").
+pub fn show(
+    ui: &mut egui::Ui,
+    oscillator: &mut Oscillator,
+    peak: f32,
+    clipped: bool,
+    index: usize,
+    oscillator_count: usize,
+    clipboard: &Option<Oscillator>,
+) -> OscillatorPanelAction {
+    let mut action = OscillatorPanelAction::None;
+    ui.horizontal(|ui| {
+        ui.heading(format!("Oscillator {}", index + 1));
+        if crate::ui::components::level_meter::show(ui, peak, clipped) {
+            action = OscillatorPanelAction::ClipReset;
+        }
+    });
+
+    ui.horizontal(|ui| {
+        if ui.button("Copy settings").clicked() {
+            action = OscillatorPanelAction::Copy;
+        }
+        if ui.add_enabled(clipboard.is_some(), egui::Button::new("Paste settings")).clicked() {
+            action = OscillatorPanelAction::Paste;
+        }
+        egui::ComboBox::from_id_source(("oscillator_swap", index))
+            .selected_text("Swap with...")
+            .show_ui(ui, |ui| {
+                for other in 0..oscillator_count {
+                    if other != index && ui.button(format!("Oscillator {}", other + 1)).clicked() {
+                        action = OscillatorPanelAction::SwapWith(other);
+                    }
+                }
+            });
+    });
+
+    ui.checkbox(&mut oscillator.enabled, "Enabled");
+    let volume_response = ui.add(egui::Slider::new(&mut oscillator.volume, 0.0..=1.0).text("Volume"));
+    assign_to_macro_menu(&volume_response, &mut action, ModTarget::OscillatorVolume(index));
+    let pan_response = ui.add(egui::Slider::new(&mut oscillator.pan, -1.0..=1.0).text("Pan"));
+    assign_to_macro_menu(&pan_response, &mut action, ModTarget::OscillatorPan(index));
+    let detune_response = ui.add(egui::Slider::new(&mut oscillator.detune_semitones, -24.0..=24.0).text("Detune (semitones)"));
+    assign_to_macro_menu(&detune_response, &mut action, ModTarget::OscillatorDetune(index));
+    ui.add(egui::Slider::new(&mut oscillator.pitch_shift_semitones, -24.0..=24.0).text("Pitch Shift (semitones)"));
+
+    if ui.button("Re-seed Noise").clicked() {
+        oscillator.reseed_noise();
+    }
+    ui.label("Picks a new noise seed for White/Pink Noise waveforms, for a different (but still reproducible) noise character.");
+
+    let mut stereo_delay_enabled = oscillator.stereo_delay.is_some();
+    if ui.checkbox(&mut stereo_delay_enabled, "Ensemble (stereo delay)").changed() {
+        oscillator.stereo_delay = stereo_delay_enabled.then(StereoDelayMod::default);
+    }
+    if let Some(stereo_delay) = &mut oscillator.stereo_delay {
+        ui.add(egui::Slider::new(&mut stereo_delay.max_delay_ms, 1.0..=30.0).text("Stereo Spread (ms)"));
+        ui.add(egui::Slider::new(&mut stereo_delay.lfo_rate, 0.05..=5.0).text("Ensemble Rate (Hz)"));
+        ui.add(egui::Slider::new(&mut stereo_delay.stereo_spread, 0.0..=1.0).text("Ensemble Width"));
+    }
+
+    ui.separator();
+    ui.label("Filter");
+    let cutoff_response = ui.add(egui::Slider::new(&mut oscillator.filter.cutoff_hz, 20.0..=20_000.0)
+        .logarithmic(true)
+        .text("Cutoff (Hz)"));
+    assign_to_macro_menu(&cutoff_response, &mut action, ModTarget::FilterCutoff(index));
+    let resonance_response = ui.add(egui::Slider::new(&mut oscillator.filter.resonance, 0.0..=1.0).text("Resonance"));
+    assign_to_macro_menu(&resonance_response, &mut action, ModTarget::FilterResonance(index));
+    ui.add(egui::Slider::new(&mut oscillator.velocity_to_filter_cutoff, 0.0..=4.0).text("Velocity -> Cutoff (oct)"));
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label("Note Range");
+        ui.add(egui::DragValue::new(&mut oscillator.midi_note_low).clamp_range(0..=oscillator.midi_note_high));
+        ui.label("-");
+        ui.add(egui::DragValue::new(&mut oscillator.midi_note_high).clamp_range(oscillator.midi_note_low..=127));
+    });
+
+    show_response_curve(ui, &oscillator.filter);
+    action
+}
+
+fn show_response_curve(ui: &mut egui::Ui, filter: &crate::core::oscillator::Filter) {
+    let curve = filter.response_curve(20.0, 20_000.0, 128);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(240.0), 80.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(30));
+
+    let min_db = -60.0;
+    let max_db = 12.0;
+    let points: Vec<egui::Pos2> = curve
+        .iter()
+        .enumerate()
+        .map(|(i, (_, db))| {
+            let x = rect.min.x + rect.width() * (i as f32 / (curve.len() - 1) as f32);
+            let t = ((db - min_db) / (max_db - min_db)).clamp(0.0, 1.0);
+            let y = rect.max.y - rect.height() * t;
+            egui::pos2(x, y)
+        })
+        .collect();
+
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.5, egui::Color32::from_rgb(100, 200, 255))));
+}
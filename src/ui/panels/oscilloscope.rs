@@ -0,0 +1,59 @@
+use crate::app::SynthApp;
+
+/// Draws `samples` into `rect` as a polyline, scaled the same way
+/// regardless of whether it's the live trace or a held ghost.
+fn waveform_points(rect: egui::Rect, samples: &[f32]) -> Vec<egui::Pos2> {
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| {
+            let x = rect.min.x + rect.width() * (i as f32 / (samples.len() - 1).max(1) as f32);
+            let y = rect.center().y - sample.clamp(-1.0, 1.0) * rect.height() * 0.5;
+            egui::pos2(x, y)
+        })
+        .collect()
+}
+
+/// Draws a real-time oscilloscope from `Analyzer::current_waveform_samples`,
+/// with a "Hold" button that freezes the current trace as a dimmed ghost
+/// behind the still-moving live one, for comparing a waveform against
+/// whatever the signal changes into afterward.
+pub fn show(ui: &mut egui::Ui, app: &mut SynthApp) {
+    app.refresh_analyzer();
+    let samples = app.analyzer.current_waveform_samples();
+
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width().min(320.0), 100.0),
+        egui::Sense::hover(),
+    );
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+
+    if let Some(held) = app.oscilloscope_hold.as_ref().filter(|held| !held.is_empty()) {
+        ui.painter().add(egui::Shape::line(
+            waveform_points(rect, held),
+            egui::Stroke::new(1.5, egui::Color32::from_rgba_unmultiplied(150, 150, 150, 128)),
+        ));
+    }
+
+    if !samples.is_empty() {
+        ui.painter().add(egui::Shape::line(
+            waveform_points(rect, &samples),
+            egui::Stroke::new(1.5, egui::Color32::from_rgb(100, 255, 150)),
+        ));
+    }
+
+    ui.horizontal(|ui| {
+        if app.oscilloscope_hold.is_some() {
+            if ui.button("Release Hold").clicked() {
+                app.release_oscilloscope_hold();
+            }
+        } else if ui.button("Hold").clicked() {
+            app.freeze_oscilloscope();
+        }
+    });
+
+    if let Some(frequency_hz) = app.analyzer.detect_dominant_pitch(app.sample_rate, 40.0, 2_000.0) {
+        let (note, cents) = crate::core::synth::frequency_to_midi_note(frequency_hz);
+        ui.label(format!("Pitch: {frequency_hz:.1} Hz (MIDI {note}, {cents:+.0}c)"));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::app::SynthApp;
+
+/// Persistent UI state for the preset browser: the "Mutate Amount" slider
+/// position and which preset's preview waveform is currently expanded,
+/// since everything else reads straight from `SynthApp`.
+pub struct PresetBrowser {
+    pub mutate_amount: f32,
+    previewing: Option<String>,
+}
+
+impl Default for PresetBrowser {
+    fn default() -> Self {
+        Self { mutate_amount: 0.2, previewing: None }
+    }
+}
+
+impl PresetBrowser {
+    /// Draws the preset list (with per-preset Load/Preview buttons) and a
+    /// "Mutate" section that nudges the currently active patch by a random
+    /// amount. "Preview" renders (and caches, via `SynthApp::preset_preview`)
+    /// a short audition clip and plots it inline below the preset; actual
+    /// playback would need a second, ad hoc `cpal` stream independent of
+    /// the live audio thread, which nothing else in this UI layer does, so
+    /// the clip is visualized rather than played back.
+    pub fn show(&mut self, ui: &mut egui::Ui, app: &mut SynthApp) {
+        ui.heading("Presets");
+
+        let mut pending_load = None;
+        let preset_names: Vec<String> = app.presets.iter().map(|preset| preset.name.clone()).collect();
+        for name in &preset_names {
+            ui.horizontal(|ui| {
+                let is_current = app.current_preset_name.as_deref() == Some(name.as_str());
+                ui.label(if is_current { format!("\u{25b6} {name}") } else { name.clone() });
+                if ui.button("Load").clicked() {
+                    pending_load = Some(name.clone());
+                }
+                if ui.button("\u{25b6} Preview").clicked() {
+                    self.previewing = Some(name.clone());
+                }
+            });
+            if self.previewing.as_deref() == Some(name.as_str()) {
+                if let Some(preview) = app.preset_preview(name) {
+                    show_preview_plot(ui, preview);
+                }
+            }
+        }
+        if let Some(name) = pending_load {
+            app.load_preset_by_name(&name);
+        }
+
+        ui.separator();
+        ui.add(egui::Slider::new(&mut self.mutate_amount, 0.0..=1.0).text("Mutate Amount"));
+        if ui.button("Mutate").clicked() {
+            app.mutate_current_patch(self.mutate_amount);
+        }
+    }
+}
+
+/// Draws `preview` (downsampled to fit the available width) as a small
+/// inline waveform plot, the same style as `oscillator_panel`'s filter
+/// response curve.
+fn show_preview_plot(ui: &mut egui::Ui, preview: &[f32]) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(320.0), 48.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+    if preview.is_empty() {
+        return;
+    }
+
+    let plot_points = rect.width().max(1.0) as usize;
+    let points: Vec<egui::Pos2> = (0..plot_points)
+        .map(|i| {
+            let sample_index = i * preview.len() / plot_points;
+            let sample = preview[sample_index.min(preview.len() - 1)];
+            let x = rect.min.x + i as f32;
+            let y = rect.center().y - sample * rect.height() * 0.5;
+            egui::pos2(x, y)
+        })
+        .collect();
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 200, 255))));
+}
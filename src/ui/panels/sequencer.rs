@@ -0,0 +1,146 @@
+use crate::app::{MidiClipRecordMode, QuantizeGrid, SynthApp};
+use crate::core::sequencer::AutomationTrack;
+
+/// Persistent UI state for the sequencer tab, separate from `SynthApp`'s
+/// `midi_player`/`automation_tracks` since it's purely about what's typed
+/// into the panel rather than audio-thread-adjacent state.
+#[derive(Default)]
+pub struct SequencerPanel {
+    pub midi_path_input: String,
+    pub midi_clip_export_path: String,
+}
+
+impl SequencerPanel {
+    /// Draws the MIDI file transport plus an automation editor: one
+    /// draggable-circle timeline per recorded `AutomationTrack`, each with
+    /// its own enable/disable toggle.
+    pub fn show(&mut self, ui: &mut egui::Ui, app: &mut SynthApp) {
+        ui.heading("Sequencer");
+
+        ui.horizontal(|ui| {
+            ui.label("MIDI file:");
+            ui.text_edit_singleline(&mut self.midi_path_input);
+            if ui.button("Load").clicked() {
+                if let Err(err) = app.load_midi_file(std::path::Path::new(&self.midi_path_input)) {
+                    eprintln!("failed to load MIDI file: {err}");
+                }
+            }
+        });
+
+        if let Some(player) = &mut app.midi_player {
+            ui.horizontal(|ui| {
+                if ui.button(if player.playing { "Pause" } else { "Play" }).clicked() {
+                    if player.playing {
+                        player.pause();
+                    } else {
+                        player.play();
+                    }
+                }
+                if ui.button("Stop").clicked() {
+                    player.stop();
+                }
+                ui.checkbox(&mut player.loop_playback, "Loop");
+            });
+            ui.checkbox(&mut app.recording_automation, "Record Automation");
+        }
+
+        ui.separator();
+        ui.heading("MIDI Clip Recording");
+        ui.label("Captures notes played via MIDI input or `note_on`/`note_off` into an exportable clip.");
+        ui.horizontal(|ui| {
+            if app.recording_midi_clip {
+                if ui.button("Stop Recording").clicked() {
+                    app.stop_midi_clip_recording();
+                }
+            } else if ui.button("Start Recording").clicked() {
+                app.start_midi_clip_recording();
+            }
+            ui.label("BPM");
+            ui.add(egui::DragValue::new(&mut app.midi_clip_bpm).clamp_range(20.0..=300.0));
+            if ui.button("Tap Tempo").clicked() {
+                app.tap_tempo();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut app.midi_clip_record_mode, MidiClipRecordMode::Overdub, "Overdub");
+            ui.selectable_value(&mut app.midi_clip_record_mode, MidiClipRecordMode::Replace, "Replace");
+            egui::ComboBox::from_id_source("midi_clip_quantize_grid")
+                .selected_text(match app.quantize_grid {
+                    QuantizeGrid::Quarter => "1/4",
+                    QuantizeGrid::Eighth => "1/8",
+                    QuantizeGrid::Sixteenth => "1/16",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.quantize_grid, QuantizeGrid::Quarter, "1/4");
+                    ui.selectable_value(&mut app.quantize_grid, QuantizeGrid::Eighth, "1/8");
+                    ui.selectable_value(&mut app.quantize_grid, QuantizeGrid::Sixteenth, "1/16");
+                });
+            ui.add(egui::Slider::new(&mut app.quantize_strength, 0.0..=1.0).text("Quantize Strength"));
+        });
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.midi_clip_export_path);
+            if ui.button("Export MIDI Clip").clicked() {
+                if let Err(err) = app.export_midi_clip(std::path::Path::new(&self.midi_clip_export_path)) {
+                    eprintln!("failed to export MIDI clip: {err}");
+                }
+            }
+        });
+
+        ui.separator();
+        ui.heading("Automation");
+
+        let mut remove_index = None;
+        for (index, track) in app.automation_tracks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut track.enabled, format!("{:?}", track.parameter));
+                if ui.button("Clear").clicked() {
+                    remove_index = Some(index);
+                }
+            });
+            show_timeline(ui, track);
+        }
+        if let Some(index) = remove_index {
+            app.automation_tracks.remove(index);
+        }
+    }
+}
+
+/// Draws `track`'s points as draggable circles on a tick/value timeline:
+/// X is tick position (scaled to the track's latest point), Y is the
+/// normalized (0.0-1.0) parameter value.
+fn show_timeline(ui: &mut egui::Ui, track: &mut AutomationTrack) {
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width().min(400.0), 60.0),
+        egui::Sense::hover(),
+    );
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(25));
+
+    let Some(max_tick) = track.points.iter().map(|point| point.tick).max() else {
+        return;
+    };
+    let max_tick = max_tick.max(1);
+    let mut dragged = false;
+
+    for point in track.points.iter_mut() {
+        let x = rect.min.x + rect.width() * (point.tick as f32 / max_tick as f32);
+        let y = rect.max.y - rect.height() * point.value.clamp(0.0, 1.0);
+        let center = egui::pos2(x, y);
+        let point_rect = egui::Rect::from_center_size(center, egui::vec2(10.0, 10.0));
+        let id = ui.id().with((track.parameter, point.tick));
+        let response = ui.interact(point_rect, id, egui::Sense::drag());
+        ui.painter()
+            .circle_filled(center, 4.0, egui::Color32::from_rgb(100, 200, 255));
+
+        if response.dragged() {
+            let delta = response.drag_delta();
+            point.value = (point.value - delta.y / rect.height()).clamp(0.0, 1.0);
+            let tick_delta = (delta.x / rect.width() * max_tick as f32) as i64;
+            point.tick = (point.tick as i64 + tick_delta).max(0) as u64;
+            dragged = true;
+        }
+    }
+
+    if dragged {
+        track.points.sort_by_key(|point| point.tick);
+    }
+}
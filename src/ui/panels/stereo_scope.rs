@@ -0,0 +1,17 @@
+use crate::core::analyzer::Analyzer;
+
+/// Draws a Lissajous/XY scope: left channel on the X axis, right on Y,
+/// showing stereo width and panning at a glance (a diagonal line is mono,
+/// a wide circle is fully decorrelated).
+pub fn show(ui: &mut egui::Ui, analyzer: &Analyzer) {
+    let pairs = analyzer.current_stereo_samples();
+    let size = ui.available_width().min(160.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+
+    for (left, right) in pairs {
+        let x = rect.center().x + left.clamp(-1.0, 1.0) * rect.width() * 0.5;
+        let y = rect.center().y - right.clamp(-1.0, 1.0) * rect.height() * 0.5;
+        ui.painter().circle_filled(egui::pos2(x, y), 0.75, egui::Color32::from_rgb(150, 200, 255));
+    }
+}
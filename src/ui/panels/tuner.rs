@@ -0,0 +1,45 @@
+use crate::app::SynthApp;
+
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+fn note_name(note: u8) -> String {
+    let octave = note as i32 / 12 - 1;
+    format!("{}{octave}", NOTE_NAMES[note as usize % 12])
+}
+
+/// Draws a needle-style chromatic tuner from `Analyzer::get_tuner_reading`:
+/// the note name, a ±50 cent scale with a center "in tune" line, and a
+/// needle at the current deviation. Shows only a "No signal" label while no
+/// note is actively playing (see `TUNER_SIGNAL_THRESHOLD`).
+pub fn show(ui: &mut egui::Ui, app: &mut SynthApp) {
+    ui.heading("Tuner");
+    app.refresh_analyzer();
+
+    let Some(reading) = app.analyzer.get_tuner_reading(app.sample_rate) else {
+        ui.label("No signal");
+        return;
+    };
+
+    ui.label(egui::RichText::new(note_name(reading.closest_note)).size(24.0));
+
+    let size = egui::vec2(ui.available_width().min(280.0), 60.0);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(20));
+
+    // Center "in tune" line.
+    ui.painter().vline(
+        rect.center().x,
+        rect.min.y..=rect.max.y,
+        egui::Stroke::new(1.5, egui::Color32::from_gray(120)),
+    );
+
+    // Needle, positioned by cents_deviation across the -50..=50 scale.
+    let fraction = (reading.cents_deviation / 50.0).clamp(-1.0, 1.0);
+    let needle_x = rect.center().x + fraction * rect.width() * 0.5;
+    ui.painter().line_segment(
+        [egui::pos2(needle_x, rect.min.y), egui::pos2(needle_x, rect.max.y)],
+        egui::Stroke::new(3.0, egui::Color32::from_rgb(220, 50, 50)),
+    );
+
+    ui.label(format!("{:+.0} cents", reading.cents_deviation));
+}
@@ -0,0 +1,22 @@
+use crate::core::synth::Synth;
+
+/// Shows the configured polyphony limit and a status dot per active voice.
+pub fn show(ui: &mut egui::Ui, synth: &Synth) {
+    let limit_text = match synth.max_polyphony {
+        Some(limit) => format!("Polyphony: {}/{limit}", synth.voices.len()),
+        None => format!("Polyphony: {} (unlimited)", synth.voices.len()),
+    };
+    ui.label(limit_text);
+
+    ui.horizontal(|ui| {
+        for voice in synth.voices.values() {
+            let color = if voice.active {
+                egui::Color32::from_rgb(100, 220, 100)
+            } else {
+                egui::Color32::from_gray(90)
+            };
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+            ui.painter().circle_filled(rect.center(), 4.0, color);
+        }
+    });
+}
@@ -0,0 +1,93 @@
+use crate::app::SynthApp;
+use std::collections::VecDeque;
+
+const FFT_SIZE: usize = 256;
+const MAX_FRAMES: usize = 64;
+
+/// A time-scrolling spectrogram: each call to `push_frame` adds one FFT
+/// frame (from `Analyzer::compute_fft`) to the bottom, and older frames
+/// scroll up, so brightness over time shows how the spectrum evolves
+/// rather than just its current snapshot.
+#[derive(Default)]
+pub struct WaterfallDisplay {
+    frames: VecDeque<Vec<[f32; 2]>>,
+}
+
+impl WaterfallDisplay {
+    /// Appends `frame`, dropping the oldest frame once `MAX_FRAMES` is
+    /// exceeded.
+    pub fn push_frame(&mut self, frame: Vec<[f32; 2]>) {
+        if self.frames.len() >= MAX_FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Computes a fresh FFT frame from `app`'s analyzer and draws the
+    /// waterfall grid: X is frequency, Y is time (newest at the bottom),
+    /// and color encodes magnitude via a blue -> green -> yellow -> red
+    /// gradient.
+    pub fn show(&mut self, ui: &mut egui::Ui, app: &mut SynthApp) {
+        app.refresh_analyzer();
+        self.push_frame(app.analyzer.compute_fft(app.sample_rate, FFT_SIZE));
+
+        let (rect, _) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width().min(320.0), 160.0),
+            egui::Sense::hover(),
+        );
+        ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(15));
+
+        if self.frames.is_empty() {
+            return;
+        }
+        let bins = self.frames.back().map(Vec::len).unwrap_or(0);
+        if bins == 0 {
+            return;
+        }
+
+        let peak_magnitude = self
+            .frames
+            .iter()
+            .flat_map(|frame| frame.iter())
+            .fold(0.0f32, |max, [_, magnitude]| max.max(*magnitude));
+        let peak_magnitude = peak_magnitude.max(f32::EPSILON);
+
+        let row_height = rect.height() / self.frames.len() as f32;
+        let column_width = rect.width() / bins as f32;
+        for (row, frame) in self.frames.iter().enumerate() {
+            let y = rect.min.y + row as f32 * row_height;
+            for (column, &[_, magnitude]) in frame.iter().enumerate() {
+                let x = rect.min.x + column as f32 * column_width;
+                let cell = egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(column_width, row_height));
+                let level = (magnitude / peak_magnitude).clamp(0.0, 1.0);
+                ui.painter().rect_filled(cell, 0.0, magnitude_to_color(level));
+            }
+        }
+    }
+}
+
+/// Maps a normalized (0.0-1.0) magnitude to a blue -> green -> yellow ->
+/// red gradient.
+fn magnitude_to_color(level: f32) -> egui::Color32 {
+    let stops = [
+        (0.0, egui::Color32::from_rgb(10, 10, 80)),
+        (0.33, egui::Color32::from_rgb(20, 160, 60)),
+        (0.66, egui::Color32::from_rgb(230, 210, 30)),
+        (1.0, egui::Color32::from_rgb(230, 40, 30)),
+    ];
+    let (mut lo, mut hi) = (stops[0], stops[stops.len() - 1]);
+    for window in stops.windows(2) {
+        if level >= window[0].0 && level <= window[1].0 {
+            lo = window[0];
+            hi = window[1];
+            break;
+        }
+    }
+    let span = (hi.0 - lo.0).max(f32::EPSILON);
+    let t = ((level - lo.0) / span).clamp(0.0, 1.0);
+    egui::Color32::from_rgb(
+        (lo.1.r() as f32 + (hi.1.r() as f32 - lo.1.r() as f32) * t) as u8,
+        (lo.1.g() as f32 + (hi.1.g() as f32 - lo.1.g() as f32) * t) as u8,
+        (lo.1.b() as f32 + (hi.1.b() as f32 - lo.1.b() as f32) * t) as u8,
+    )
+}
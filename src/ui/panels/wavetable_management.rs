@@ -0,0 +1,202 @@
+use crate::core::synth::samples::{load_sample, ChannelConversion, CustomWavetable, PlaybackMode};
+use crate::core::synth::sf2::{load_sf2, Sf2File};
+use std::path::Path;
+
+const ONE_CYCLE_LENGTH: usize = 2048;
+
+/// Formats a dBFS reading from `CustomWavetable::dc_offset_dbfs`, spelling
+/// out the "no measurable offset" case rather than printing `-inf dB`.
+fn format_dbfs(dbfs: f32) -> String {
+    if dbfs.is_finite() {
+        format!("{dbfs:.1} dBFS")
+    } else {
+        "none".to_string()
+    }
+}
+
+fn conversion_label(conversion: ChannelConversion) -> &'static str {
+    match conversion {
+        ChannelConversion::MixDown => "Mix Down",
+        ChannelConversion::LeftOnly => "Left Only",
+        ChannelConversion::RightOnly => "Right Only",
+        ChannelConversion::MidSide => "Mid/Side (mid)",
+    }
+}
+
+/// Draws the custom wavetable management section: name, frame count,
+/// sample rate (with a mismatch warning), trim/normalize/crossfade tools,
+/// and WAV/SoundFont import plus WAV export. `export_path`/`import_path`/
+/// `import_conversion`/`loop_crossfade_samples`/`sf2_path`/`sf2_file`/
+/// `sf2_selected_preset` are the caller's persistent buffers for those
+/// fields (this panel is a stateless free function, so it can't own them
+/// itself). `import_status`/`sf2_status` are overwritten with the outcome
+/// of the last import attempt for their respective section.
+#[allow(clippy::too_many_arguments)]
+pub fn show(
+    ui: &mut egui::Ui,
+    wavetable: &mut CustomWavetable,
+    synth_sample_rate: u32,
+    export_path: &mut String,
+    import_path: &mut String,
+    import_conversion: &mut ChannelConversion,
+    import_status: &mut Option<String>,
+    loop_crossfade_samples: &mut usize,
+    sf2_path: &mut String,
+    sf2_file: &mut Option<Sf2File>,
+    sf2_selected_preset: &mut usize,
+    sf2_status: &mut Option<String>,
+    dragging_files: bool,
+) {
+    ui.heading("Custom Wavetable");
+
+    if dragging_files {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 32.0), egui::Sense::hover());
+        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 200, 255)));
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "Drop WAV file here",
+            egui::FontId::proportional(14.0),
+            egui::Color32::from_rgb(100, 200, 255),
+        );
+    }
+    ui.label(format!("{} ({} frame(s))", wavetable.name, wavetable.frames.len()));
+
+    ui.horizontal(|ui| {
+        ui.label(format!("{} Hz", wavetable.sample_rate));
+        if wavetable.sample_rate != synth_sample_rate {
+            ui.colored_label(
+                egui::Color32::from_rgb(230, 180, 40),
+                format!(
+                    "\u{26a0} loaded at {} Hz, synth runs at {} Hz — will play at the wrong pitch",
+                    wavetable.sample_rate, synth_sample_rate
+                ),
+            );
+        }
+    });
+
+    ui.horizontal(|ui| {
+        if ui.button("Trim silence").clicked() {
+            wavetable.trim(0.001);
+        }
+        if ui.button("Normalize").clicked() {
+            wavetable.normalize();
+        }
+        if ui.button("Remove DC Offset").clicked() {
+            wavetable.remove_dc_offset();
+        }
+    });
+    ui.label(format!("DC Offset: {}", format_dbfs(wavetable.dc_offset_dbfs())));
+
+    if wavetable.playback_mode == PlaybackMode::Looping {
+        ui.horizontal(|ui| {
+            ui.label("Loop crossfade (samples)");
+            ui.add(egui::DragValue::new(loop_crossfade_samples).clamp_range(0..=4096));
+            if ui.button("Apply Crossfade").clicked() {
+                wavetable.apply_loop_crossfade(*loop_crossfade_samples);
+            }
+        });
+    }
+
+    ui.separator();
+    ui.label("Import WAV");
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(import_path);
+        egui::ComboBox::from_id_source("wavetable_import_conversion")
+            .selected_text(conversion_label(*import_conversion))
+            .show_ui(ui, |ui| {
+                for conversion in
+                    [ChannelConversion::MixDown, ChannelConversion::LeftOnly, ChannelConversion::RightOnly, ChannelConversion::MidSide]
+                {
+                    ui.selectable_value(import_conversion, conversion, conversion_label(conversion));
+                }
+            });
+        if ui.button("Import").clicked() {
+            match load_sample(Path::new(import_path.as_str()), synth_sample_rate, *import_conversion) {
+                Ok((loaded, report)) => {
+                    *wavetable = loaded;
+                    *import_status = Some(format!(
+                        "Loaded {} channel(s), folded with {} ({} Hz{})",
+                        report.source_channels,
+                        conversion_label(report.conversion_applied),
+                        report.loaded_sample_rate,
+                        if report.needs_resample { ", rate mismatch — will play mistuned" } else { "" },
+                    ));
+                }
+                Err(error) => *import_status = Some(format!("Import failed: {error}")),
+            }
+        }
+    });
+    if let Some(status) = import_status {
+        ui.label(status.as_str());
+    }
+
+    ui.separator();
+    ui.label("Import SoundFont");
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(sf2_path);
+        if ui.button("Load").clicked() {
+            match load_sf2(Path::new(sf2_path.as_str())) {
+                Ok(loaded) => {
+                    *sf2_selected_preset = 0;
+                    *sf2_status = Some(format!("Loaded {} preset(s)", loaded.presets().len()));
+                    *sf2_file = Some(loaded);
+                }
+                Err(error) => {
+                    *sf2_file = None;
+                    *sf2_status = Some(format!("Load failed: {error}"));
+                }
+            }
+        }
+    });
+    if let Some(file) = sf2_file {
+        let presets = file.presets();
+        if !presets.is_empty() {
+            *sf2_selected_preset = (*sf2_selected_preset).min(presets.len() - 1);
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("sf2_preset_select")
+                    .selected_text(format!(
+                        "{:03}:{:03} {}",
+                        presets[*sf2_selected_preset].bank, presets[*sf2_selected_preset].program, presets[*sf2_selected_preset].name
+                    ))
+                    .show_ui(ui, |ui| {
+                        for (index, preset) in presets.iter().enumerate() {
+                            ui.selectable_value(
+                                sf2_selected_preset,
+                                index,
+                                format!("{:03}:{:03} {}", preset.bank, preset.program, preset.name),
+                            );
+                        }
+                    });
+                if ui.button("Import Preset").clicked() {
+                    match file.load_preset(Path::new(sf2_path.as_str()), *sf2_selected_preset) {
+                        Ok((loaded, report)) => {
+                            *wavetable = loaded;
+                            *sf2_status = Some(format!("Loaded \"{}\" ({} Hz)", report.sample_name, report.sample_rate));
+                        }
+                        Err(error) => *sf2_status = Some(format!("Import failed: {error}")),
+                    }
+                }
+            });
+        }
+    }
+    if let Some(status) = sf2_status {
+        ui.label(status.as_str());
+    }
+
+    ui.separator();
+    ui.label("Export WAV");
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(export_path);
+        if ui.button("Export full").clicked() {
+            if let Err(error) = wavetable.export_wav(Path::new(export_path.as_str())) {
+                ui.colored_label(egui::Color32::RED, format!("Export failed: {error}"));
+            }
+        }
+        if ui.button("Export one cycle").clicked() {
+            if let Err(error) = wavetable.export_as_one_cycle(Path::new(export_path.as_str()), ONE_CYCLE_LENGTH) {
+                ui.colored_label(egui::Color32::RED, format!("Export failed: {error}"));
+            }
+        }
+    });
+}